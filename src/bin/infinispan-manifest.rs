@@ -0,0 +1,69 @@
+//! Applies a manifest of caches, counters and schemas to an Infinispan server.
+//!
+//! ```text
+//! infinispan-manifest <manifest.yaml|manifest.toml>
+//! ```
+//!
+//! The server and the credentials are read from the `INFINISPAN_URL`
+//! (default: "http://localhost:11222"), `INFINISPAN_USERNAME` and
+//! `INFINISPAN_PASSWORD` environment variables. It exits with an error when
+//! some resource differs from the manifest and cannot be updated.
+
+use std::env;
+use std::error::Error;
+use std::process::ExitCode;
+
+use infinispan::manifest::{self, Manifest};
+use infinispan::Infinispan;
+
+const DEFAULT_URL: &str = "http://localhost:11222";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+
+    let path = match (args.next(), args.next()) {
+        (Some(path), None) => path,
+        _ => {
+            eprintln!("usage: infinispan-manifest <manifest.yaml|manifest.toml>");
+            return ExitCode::from(2);
+        }
+    };
+
+    let client = Infinispan::new(
+        env::var("INFINISPAN_URL").unwrap_or_else(|_| DEFAULT_URL.into()),
+        env::var("INFINISPAN_USERNAME").unwrap_or_default(),
+        env::var("INFINISPAN_PASSWORD").unwrap_or_default(),
+    );
+
+    let result = match Manifest::from_path(&path) {
+        Ok(manifest) => manifest::apply(&client, &manifest).await,
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(report) => {
+            for resource in &report.resources {
+                println!("{}", resource);
+            }
+
+            if report.has_drift() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(err) => {
+            eprint!("error: {}", err);
+
+            let mut source = err.source();
+            while let Some(err) = source {
+                eprint!(": {}", err);
+                source = err.source();
+            }
+
+            eprintln!();
+            ExitCode::FAILURE
+        }
+    }
+}
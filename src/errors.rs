@@ -1,7 +1,67 @@
+use http::StatusCode;
 use thiserror::Error;
 
+use crate::reconcile::AttributeDiff;
+use crate::request::counters::CounterVal;
+
 #[derive(Error, Debug)]
 pub enum InfinispanError {
     #[error("error while sending the request to Infinispan")]
     Connection(#[from] reqwest::Error),
+
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("unexpected response from Infinispan ({status}): {body}")]
+    UnexpectedResponse { status: StatusCode, body: String },
+
+    #[error("error while parsing the response from Infinispan")]
+    InvalidResponse(#[from] serde_json::Error),
+
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// An update went beyond the bounds of a strong counter.
+    /// [`Infinispan::run_typed`](crate::Infinispan::run_typed) reads them
+    /// from the configuration of the counter. They are `None` when the
+    /// counter does not have them, or when they could not be read.
+    #[error("the counter reached one of its bounds ({})", format_bounds(.lower, .upper))]
+    CounterOutOfBounds {
+        lower: Option<CounterVal>,
+        upper: Option<CounterVal>,
+    },
+
+    #[error("the configuration of cache {name} does not match: {}", format_diffs(.diffs))]
+    CacheConfigMismatch {
+        name: String,
+        diffs: Vec<AttributeDiff>,
+    },
+}
+
+fn format_bounds(lower: &Option<CounterVal>, upper: &Option<CounterVal>) -> String {
+    let format_bound = |bound: &Option<CounterVal>| match bound {
+        Some(bound) => bound.to_string(),
+        None => "unknown".into(),
+    };
+
+    format!(
+        "lower: {}, upper: {}",
+        format_bound(lower),
+        format_bound(upper)
+    )
+}
+
+fn format_diffs(diffs: &[AttributeDiff]) -> String {
+    if diffs.is_empty() {
+        return "the server reports differences that could not be located".into();
+    }
+
+    diffs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
@@ -136,14 +136,23 @@
 #![deny(clippy::all, clippy::cargo)]
 #![allow(clippy::multiple_crate_versions)]
 
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
+use std::sync::Arc;
 
+use http::StatusCode;
 use reqwest::Response;
 
 use crate::errors::InfinispanError;
-use crate::request::ToHttpRequest;
+use crate::near_cache::{NearCache, NearCacheConfig, NearCacheMetrics};
+use crate::request::counters::{self, Counter};
+use crate::request::{ParseResponse, ToHttpRequest};
 
 pub mod errors;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+pub mod near_cache;
+pub mod reconcile;
 pub mod request;
 
 #[derive(Debug, Clone)]
@@ -151,6 +160,7 @@ pub struct Infinispan {
     base_url: String,
     http_client: reqwest::Client,
     basic_auth_encoded_val: String,
+    near_caches: HashMap<String, Arc<NearCache>>,
 }
 
 impl Infinispan {
@@ -166,10 +176,113 @@ impl Infinispan {
                 username.as_ref(),
                 password.as_ref(),
             ),
+            near_caches: HashMap::new(),
         }
     }
 
+    /// Keeps the entries of a cache read with [`Infinispan::get_entry`] in
+    /// memory. They are invalidated with the events of the cache, and while
+    /// the events cannot be received, all the reads go to the server.
+    ///
+    /// Only [`Infinispan::get_entry`] uses the near cache, so it must be used
+    /// instead of [`entries::get`](request::entries::get) to benefit from it.
+    /// Reads sent with [`Infinispan::run`], like `run(&entries::get(..))`,
+    /// always go to the server.
+    ///
+    /// The events are received in a task of the current Tokio runtime, so it
+    /// fails with [`InfinispanError::InvalidConfig`] when called outside of
+    /// one.
+    pub fn with_near_cache(
+        mut self,
+        cache_name: impl Into<String>,
+        config: NearCacheConfig,
+    ) -> Result<Self, InfinispanError> {
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            InfinispanError::InvalidConfig("a near cache needs a Tokio runtime".into())
+        })?;
+
+        let cache_name = cache_name.into();
+
+        // The listener keeps a client, which must not keep the near caches
+        // alive.
+        let listener_client = Self {
+            near_caches: HashMap::new(),
+            ..self.clone()
+        };
+
+        let near_cache = NearCache::start(&runtime, &listener_client, cache_name.clone(), config);
+        self.near_caches.insert(cache_name, near_cache);
+        Ok(self)
+    }
+
+    /// Returns the value of an entry, or `None` if it does not exist. The
+    /// value is read from the near cache of the cache when there is one.
+    pub async fn get_entry(
+        &self,
+        cache_name: impl AsRef<str>,
+        entry_name: impl AsRef<str>,
+    ) -> Result<Option<String>, InfinispanError> {
+        let near_cache = self.near_caches.get(cache_name.as_ref());
+
+        if let Some(value) = near_cache.and_then(|near_cache| near_cache.get(entry_name.as_ref())) {
+            return Ok(Some(value));
+        }
+
+        let generation = near_cache.map(|near_cache| near_cache.generation());
+
+        let req = request::entries::get(cache_name.as_ref(), entry_name.as_ref());
+        let res = self.run(&req).await?;
+        let status = res.status();
+        let body = res.text_with_charset("utf-8").await?;
+
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !status.is_success() {
+            return Err(InfinispanError::UnexpectedResponse { status, body });
+        }
+
+        if let (Some(near_cache), Some(generation)) = (near_cache, generation) {
+            near_cache.insert(entry_name.as_ref(), body.clone(), generation);
+        }
+
+        Ok(Some(body))
+    }
+
+    /// Returns the configuration of every counter, by name. The server only
+    /// lists the names, so the configuration of each counter is read with
+    /// [`counters::get_config`]. Counters
+    /// deleted in the meantime are skipped.
+    pub async fn counter_configs(&self) -> Result<BTreeMap<String, Counter>, InfinispanError> {
+        let names = self.run_typed(&counters::list()).await?;
+
+        let mut configs = BTreeMap::new();
+
+        for name in names {
+            match self.run_typed(&counters::get_config(&name)).await {
+                Ok(config) => {
+                    configs.insert(name, config);
+                }
+                Err(InfinispanError::UnexpectedResponse { status, .. })
+                    if status == StatusCode::NOT_FOUND => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(configs)
+    }
+
+    /// Metrics of the near cache of a cache, if it has one.
+    pub fn near_cache_metrics(&self, cache_name: impl AsRef<str>) -> Option<NearCacheMetrics> {
+        self.near_caches
+            .get(cache_name.as_ref())
+            .map(|near_cache| near_cache.metrics())
+    }
+
     pub async fn run<R: ToHttpRequest>(&self, request: &R) -> Result<Response, InfinispanError> {
+        request.validate()?;
+
         let http_req = request.to_http_req(&self.base_url, &self.basic_auth_encoded_val);
 
         let res = self
@@ -180,6 +293,27 @@ impl Infinispan {
         Ok(res)
     }
 
+    /// Runs the request and parses its response. Responses with a status
+    /// other than 2xx are returned as
+    /// [`InfinispanError::UnexpectedResponse`].
+    pub async fn run_typed<R: ToHttpRequest + ParseResponse>(
+        &self,
+        request: &R,
+    ) -> Result<R::Output, InfinispanError> {
+        let res = self.run(request).await?;
+        let status = res.status();
+        let body = res.text_with_charset("utf-8").await?;
+
+        match request.parse_response(status, &body) {
+            // The server does not say which bounds the counter has.
+            Err(InfinispanError::CounterOutOfBounds { .. }) => {
+                let http_req = request.to_http_req(&self.base_url, &self.basic_auth_encoded_val);
+                Err(counters::out_of_bounds_error(self, http_req.uri().path()).await)
+            }
+            result => result,
+        }
+    }
+
     fn basic_auth_encoded_value(username: &str, password: &str) -> String {
         format!(
             "Basic {}",
@@ -0,0 +1,486 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::errors::InfinispanError;
+use crate::reconcile::{self, AttributeDiff, Reconciliation};
+use crate::request::caches::Cache;
+use crate::request::counters::{
+    self, Counter, CounterStorage, CounterType, CreateStrongCounterReq, CreateWeakCounterReq,
+};
+use crate::request::{schemas, ToHttpRequest};
+use crate::Infinispan;
+
+/// Caches, counters and Protobuf schemas that should exist in the server.
+///
+/// It can be written in YAML or TOML. The configuration of each cache uses
+/// the Infinispan JSON format:
+///
+/// ```yaml
+/// schemas:
+///   - name: person.proto
+///     path: schemas/person.proto
+/// caches:
+///   - name: sessions
+///     configuration:
+///       distributed-cache:
+///         mode: SYNC
+///         memory:
+///           max-count: 10000
+/// counters:
+///   - name: requests
+///     type: strong
+///     initial-value: 0
+///     lower-bound: 0
+///     upper-bound: 1000
+///     storage: PERSISTENT
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub schemas: Vec<SchemaSpec>,
+    #[serde(default)]
+    pub caches: Vec<CacheSpec>,
+    #[serde(default)]
+    pub counters: Vec<CounterSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSpec {
+    pub name: String,
+    /// Content of the schema. It is read from `path` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Path of the schema file, relative to the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheSpec {
+    pub name: String,
+    pub configuration: Cache,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CounterSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub counter_type: CounterType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_value: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_bound: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upper_bound: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<CounterStorage>,
+    /// Only for weak counters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_level: Option<u32>,
+}
+
+impl CounterSpec {
+    // Builds the request that creates the counter, so that the manifest is
+    // validated like the requests of `counters`.
+    fn create_request(&self) -> Result<CreateCounter, InfinispanError> {
+        match self.counter_type {
+            CounterType::Weak => {
+                if self.lower_bound.is_some() || self.upper_bound.is_some() {
+                    return Err(InfinispanError::InvalidManifest(format!(
+                        "counter {}: only strong counters can have bounds",
+                        self.name
+                    )));
+                }
+
+                let mut req = counters::create_weak(&self.name);
+
+                if let Some(initial_value) = self.initial_value {
+                    req = req.with_value(initial_value);
+                }
+                if let Some(storage) = self.storage {
+                    req = req.with_storage(storage);
+                }
+                if let Some(concurrency_level) = self.concurrency_level {
+                    req = req.with_concurrency_level(concurrency_level);
+                }
+
+                Ok(CreateCounter::Weak(req))
+            }
+            CounterType::Strong => {
+                if self.concurrency_level.is_some() {
+                    return Err(InfinispanError::InvalidManifest(format!(
+                        "counter {}: only weak counters have a concurrency level",
+                        self.name
+                    )));
+                }
+
+                let mut req = counters::create_strong(&self.name);
+
+                if let Some(initial_value) = self.initial_value {
+                    req = req.with_value(initial_value);
+                }
+                if let Some(storage) = self.storage {
+                    req = req.with_storage(storage);
+                }
+                if let Some(lower_bound) = self.lower_bound {
+                    req = req.with_lower_bound(lower_bound);
+                }
+                if let Some(upper_bound) = self.upper_bound {
+                    req = req.with_upper_bound(upper_bound);
+                }
+
+                Ok(CreateCounter::Strong(req))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        self.create_request()?.validate()
+    }
+}
+
+enum CreateCounter {
+    Weak(CreateWeakCounterReq),
+    Strong(CreateStrongCounterReq),
+}
+
+impl CreateCounter {
+    fn validate(&self) -> Result<(), InfinispanError> {
+        match self {
+            CreateCounter::Weak(req) => req.validate(),
+            CreateCounter::Strong(req) => req.validate(),
+        }
+    }
+
+    fn counter(&self) -> Counter {
+        match self {
+            CreateCounter::Weak(req) => req.counter(),
+            CreateCounter::Strong(req) => req.counter(),
+        }
+    }
+
+    async fn run(&self, client: &Infinispan) -> Result<(), InfinispanError> {
+        match self {
+            CreateCounter::Weak(req) => client.run_typed(req).await,
+            CreateCounter::Strong(req) => client.run_typed(req).await,
+        }
+    }
+}
+
+impl Manifest {
+    pub fn from_yaml(yaml: &str) -> Result<Self, InfinispanError> {
+        let value: Value = serde_yaml::from_str(yaml)
+            .map_err(|e| InfinispanError::InvalidManifest(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, InfinispanError> {
+        let value: Value =
+            toml::from_str(toml).map_err(|e| InfinispanError::InvalidManifest(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    // serde_yaml expects enums like `Cache` to be written as YAML tags, so
+    // manifests are read into JSON first to accept the JSON configuration
+    // format in both YAML and TOML.
+    fn from_value(value: Value) -> Result<Self, InfinispanError> {
+        serde_json::from_value(value).map_err(|e| InfinispanError::InvalidManifest(e.to_string()))
+    }
+
+    /// Reads a manifest in YAML (".yaml" or ".yml") or TOML (".toml") and the
+    /// schema files it references.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, InfinispanError> {
+        let path = path.as_ref();
+        let content = read_file(path)?;
+
+        let mut manifest = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml(&content)?,
+            Some("toml") => Self::from_toml(&content)?,
+            _ => {
+                return Err(InfinispanError::InvalidManifest(format!(
+                    "{} is not a YAML or TOML file",
+                    path.display()
+                )))
+            }
+        };
+
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        for schema in &mut manifest.schemas {
+            if let (None, Some(schema_path)) = (&schema.content, &schema.path) {
+                schema.content = Some(read_file(&base_dir.join(schema_path))?);
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> Result<(), InfinispanError> {
+        for schema in &self.schemas {
+            if schema.content.is_none() {
+                return Err(InfinispanError::InvalidManifest(format!(
+                    "schema {} has no content",
+                    schema.name
+                )));
+            }
+        }
+
+        for cache in &self.caches {
+            cache.configuration.validate()?;
+        }
+
+        for counter in &self.counters {
+            counter.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Schema,
+    Cache,
+    Counter,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceKind::Schema => write!(f, "schema"),
+            ResourceKind::Cache => write!(f, "cache"),
+            ResourceKind::Counter => write!(f, "counter"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceStatus {
+    Created,
+    Unchanged,
+    /// The resource was different, and it was updated.
+    Updated(Vec<AttributeDiff>),
+    /// The resource is different, and it cannot be updated without
+    /// recreating it.
+    Drifted(Vec<AttributeDiff>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceReport {
+    pub kind: ResourceKind,
+    pub name: String,
+    pub status: ResourceStatus,
+}
+
+impl fmt::Display for ResourceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: ", self.kind, self.name)?;
+
+        let diffs = match &self.status {
+            ResourceStatus::Created => return write!(f, "created"),
+            ResourceStatus::Unchanged => return write!(f, "unchanged"),
+            ResourceStatus::Updated(diffs) => {
+                write!(f, "updated")?;
+                diffs
+            }
+            ResourceStatus::Drifted(diffs) => {
+                write!(f, "drifted")?;
+                diffs
+            }
+        };
+
+        for diff in diffs {
+            write!(f, "\n  {}", diff)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ApplyReport {
+    pub resources: Vec<ResourceReport>,
+}
+
+impl ApplyReport {
+    pub fn has_drift(&self) -> bool {
+        self.resources
+            .iter()
+            .any(|resource| matches!(resource.status, ResourceStatus::Drifted(_)))
+    }
+}
+
+/// Creates the resources of the manifest that do not exist and reports the
+/// ones that differ. It can be run several times against the same server.
+///
+/// Schemas are applied first, so that caches can reference their types. The
+/// registered schemas are fetched once to check the entities of the indexed
+/// caches before creating them.
+/// Schemas with a different content and caches that only differ in mutable
+/// attributes are updated. Caches with other differences and counters that
+/// differ are reported as drifted.
+pub async fn apply(
+    client: &Infinispan,
+    manifest: &Manifest,
+) -> Result<ApplyReport, InfinispanError> {
+    manifest.validate()?;
+
+    let mut report = ApplyReport::default();
+
+    for schema in &manifest.schemas {
+        report.resources.push(ResourceReport {
+            kind: ResourceKind::Schema,
+            name: schema.name.clone(),
+            status: apply_schema(client, schema).await?,
+        });
+    }
+
+    // Fetched once for all the caches, and only if some cache is indexed.
+    let has_indexed_caches = manifest.caches.iter().any(|cache| {
+        cache
+            .configuration
+            .indexing()
+            .is_some_and(|indexing| indexing.is_enabled())
+    });
+
+    let schemas = if has_indexed_caches {
+        reconcile::registered_schemas(client).await?
+    } else {
+        Vec::new()
+    };
+
+    for cache in &manifest.caches {
+        report.resources.push(ResourceReport {
+            kind: ResourceKind::Cache,
+            name: cache.name.clone(),
+            status: apply_cache(client, cache, &schemas).await?,
+        });
+    }
+
+    for counter in &manifest.counters {
+        report.resources.push(ResourceReport {
+            kind: ResourceKind::Counter,
+            name: counter.name.clone(),
+            status: apply_counter(client, counter).await?,
+        });
+    }
+
+    Ok(report)
+}
+
+async fn apply_schema(
+    client: &Infinispan,
+    schema: &SchemaSpec,
+) -> Result<ResourceStatus, InfinispanError> {
+    let content = schema.content.clone().unwrap_or_default();
+
+    let resp = client.run(&schemas::get(&schema.name)).await?;
+    let status = resp.status();
+
+    if status == StatusCode::NOT_FOUND {
+        client
+            .run_typed(&schemas::create(&schema.name, content))
+            .await?;
+        return Ok(ResourceStatus::Created);
+    }
+
+    let current = resp.text_with_charset("utf-8").await?;
+
+    if !status.is_success() {
+        return Err(InfinispanError::UnexpectedResponse {
+            status,
+            body: current,
+        });
+    }
+
+    if current.trim() == content.trim() {
+        return Ok(ResourceStatus::Unchanged);
+    }
+
+    client
+        .run_typed(&schemas::update(&schema.name, content.clone()))
+        .await?;
+
+    Ok(ResourceStatus::Updated(vec![AttributeDiff {
+        attribute: "content".into(),
+        expected: content.into(),
+        actual: Some(current.into()),
+        mutable: true,
+    }]))
+}
+
+async fn apply_cache(
+    client: &Infinispan,
+    cache: &CacheSpec,
+    schemas: &[String],
+) -> Result<ResourceStatus, InfinispanError> {
+    match reconcile::ensure_cache_with_schemas(client, &cache.name, &cache.configuration, schemas)
+        .await
+    {
+        Ok(Reconciliation::Created) => Ok(ResourceStatus::Created),
+        Ok(Reconciliation::Unchanged) => Ok(ResourceStatus::Unchanged),
+        Ok(Reconciliation::Updated(diffs)) => Ok(ResourceStatus::Updated(diffs)),
+        Err(InfinispanError::CacheConfigMismatch { diffs, .. }) => {
+            Ok(ResourceStatus::Drifted(diffs))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+async fn apply_counter(
+    client: &Infinispan,
+    counter: &CounterSpec,
+) -> Result<ResourceStatus, InfinispanError> {
+    let create = counter.create_request()?;
+
+    let current = match client.run_typed(&counters::get_config(&counter.name)).await {
+        Ok(current) => current,
+        Err(InfinispanError::UnexpectedResponse { status, .. })
+            if status == StatusCode::NOT_FOUND =>
+        {
+            create.run(client).await?;
+            return Ok(ResourceStatus::Created);
+        }
+        Err(err) => return Err(err),
+    };
+
+    let diffs = diff_counters(&create.counter(), &current);
+
+    if diffs.is_empty() {
+        Ok(ResourceStatus::Unchanged)
+    } else {
+        Ok(ResourceStatus::Drifted(diffs))
+    }
+}
+
+// Only the attributes set in the manifest are compared. The server adds the
+// rest, like the name.
+fn diff_counters(expected: &Counter, actual: &Counter) -> Vec<AttributeDiff> {
+    if expected.counter_type() != actual.counter_type() {
+        return vec![AttributeDiff {
+            attribute: "type".into(),
+            expected: json!(expected.counter_type()),
+            actual: Some(json!(actual.counter_type())),
+            mutable: false,
+        }];
+    }
+
+    let attributes = |counter: &Counter| match counter {
+        Counter::Weak(counter) => json!(counter),
+        Counter::Strong(counter) => json!(counter),
+    };
+
+    reconcile::diff_json(&attributes(expected), &attributes(actual), &[])
+}
+
+fn read_file(path: &Path) -> Result<String, InfinispanError> {
+    fs::read_to_string(path).map_err(|e| {
+        InfinispanError::InvalidManifest(format!("cannot read {}: {}", path.display(), e))
+    })
+}
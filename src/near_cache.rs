@@ -0,0 +1,316 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+use crate::request::events::{self, SubscriptionEvent};
+use crate::Infinispan;
+
+/// Configuration of a near cache, enabled with
+/// [`Infinispan::with_near_cache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NearCacheConfig {
+    max_entries: usize,
+    ttl: Option<Duration>,
+}
+
+impl NearCacheConfig {
+    /// Keeps up to `max_entries` entries. When it is full, the least recently
+    /// used entry is evicted.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            ttl: None,
+        }
+    }
+
+    /// Time after which an entry is read again from the server, even if it
+    /// did not change.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NearCacheMetrics {
+    /// Reads returned from the near cache.
+    pub hits: u64,
+    /// Reads sent to the server, including the ones made while the near cache
+    /// was not connected.
+    pub misses: u64,
+    /// Entries removed because the near cache was full.
+    pub evictions: u64,
+    /// Entries removed because they changed in the server.
+    pub invalidations: u64,
+    /// Times that the near cache was emptied because the event stream was
+    /// disconnected.
+    pub flushes: u64,
+}
+
+impl NearCacheMetrics {
+    pub fn hit_ratio(&self) -> f64 {
+        let reads = self.hits + self.misses;
+
+        if reads == 0 {
+            0.0
+        } else {
+            self.hits as f64 / reads as f64
+        }
+    }
+}
+
+struct Entry {
+    value: String,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+// Entries by key, and keys by last use to find the least recently used.
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<String, Entry>,
+    by_last_use: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Lru {
+    fn get(&mut self, key: &str, now: Instant) -> Option<String> {
+        let entry = self.entries.get_mut(key)?;
+
+        if entry.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            self.remove(key);
+            return None;
+        }
+
+        self.clock += 1;
+        self.by_last_use.remove(&entry.last_used);
+        self.by_last_use.insert(self.clock, key.to_string());
+        entry.last_used = self.clock;
+
+        Some(entry.value.clone())
+    }
+
+    // Returns whether an entry was evicted to make room for this one.
+    fn insert(
+        &mut self,
+        key: String,
+        value: String,
+        expires_at: Option<Instant>,
+        max: usize,
+    ) -> bool {
+        self.remove(&key);
+
+        let mut evicted = false;
+
+        if self.entries.len() >= max {
+            if let Some((_, lru_key)) = self.by_last_use.pop_first() {
+                self.entries.remove(&lru_key);
+                evicted = true;
+            }
+        }
+
+        self.clock += 1;
+        self.by_last_use.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                expires_at,
+                last_used: self.clock,
+            },
+        );
+
+        evicted
+    }
+
+    fn remove(&mut self, key: &str) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.by_last_use.remove(&entry.last_used);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.by_last_use.clear();
+    }
+}
+
+/// Entries of a remote cache kept in memory by the client. It is only used
+/// while it is connected to the events of the remote cache, so that changes
+/// made by other clients invalidate its entries.
+pub(crate) struct NearCache {
+    config: NearCacheConfig,
+    lru: Mutex<Lru>,
+    connected: AtomicBool,
+    // Increased on every invalidation, so that a value read from the server
+    // is not stored if the entry changed while it was being read.
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+    flushes: AtomicU64,
+    listener: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl fmt::Debug for NearCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NearCache")
+            .field("config", &self.config)
+            .field("connected", &self.connected.load(Ordering::Relaxed))
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+impl NearCache {
+    /// Creates the near cache and starts listening to the events of the
+    /// remote cache in the given runtime.
+    pub(crate) fn start(
+        runtime: &Handle,
+        client: &Infinispan,
+        cache_name: impl Into<String>,
+        config: NearCacheConfig,
+    ) -> Arc<Self> {
+        let near_cache = Arc::new(Self {
+            config,
+            lru: Mutex::new(Lru::default()),
+            connected: AtomicBool::new(false),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            flushes: AtomicU64::new(0),
+            listener: Mutex::new(None),
+        });
+
+        let events = events::subscribe_with_connections(client, events::listen(cache_name));
+        let weak = Arc::downgrade(&near_cache);
+
+        let listener = runtime.spawn(listen(weak, events));
+        *near_cache.listener.lock().unwrap() = Some(listener);
+
+        near_cache
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        let value = if self.connected.load(Ordering::Acquire) {
+            self.lru.lock().unwrap().get(key, Instant::now())
+        } else {
+            None
+        };
+
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        value
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Stores a value read from the server when the read started at
+    /// `generation`, if nothing was invalidated since then.
+    pub(crate) fn insert(&self, key: &str, value: String, generation: u64) {
+        if !self.connected.load(Ordering::Acquire) {
+            return;
+        }
+
+        let expires_at = self.config.ttl.map(|ttl| Instant::now() + ttl);
+        let mut lru = self.lru.lock().unwrap();
+
+        // Invalidations take the lock, so the generation cannot change while
+        // the value is inserted.
+        if self.generation() != generation {
+            return;
+        }
+
+        if lru.insert(key.into(), value, expires_at, self.config.max_entries) {
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> NearCacheMetrics {
+        NearCacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            flushes: self.flushes.load(Ordering::Relaxed),
+        }
+    }
+
+    fn invalidate(&self, key: &str) {
+        let mut lru = self.lru.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+
+        if lru.remove(key) {
+            self.invalidations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn set_connected(&self, connected: bool) {
+        let mut lru = self.lru.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+
+        // Events can be missed while disconnected, so the entries cannot be
+        // trusted anymore.
+        if !connected && self.connected.load(Ordering::Acquire) {
+            lru.clear();
+            self.flushes.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.connected.store(connected, Ordering::Release);
+    }
+}
+
+impl Drop for NearCache {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.get_mut().unwrap().take() {
+            listener.abort();
+        }
+    }
+}
+
+async fn listen<S>(near_cache: Weak<NearCache>, events: S)
+where
+    S: futures_util::Stream<Item = Result<SubscriptionEvent, crate::errors::InfinispanError>>,
+{
+    futures_util::pin_mut!(events);
+
+    while let Some(event) = events.next().await {
+        let near_cache = match near_cache.upgrade() {
+            Some(near_cache) => near_cache,
+            None => return,
+        };
+
+        match event {
+            Ok(SubscriptionEvent::Connected) => near_cache.set_connected(true),
+            Ok(SubscriptionEvent::Disconnected) => near_cache.set_connected(false),
+            Ok(SubscriptionEvent::Event(event)) => near_cache.invalidate(&event.key),
+            // The stream ends after an error, so the near cache stays
+            // disabled and every read goes to the server.
+            Err(_) => near_cache.set_connected(false),
+        }
+    }
+
+    if let Some(near_cache) = near_cache.upgrade() {
+        near_cache.set_connected(false);
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+
+use http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::errors::InfinispanError;
+use crate::request::caches::modes::{Indexing, MemorySize};
+use crate::request::caches::{self, Cache};
+use crate::request::{check_status, schemas, ParseResponse, Request};
+use crate::Infinispan;
+
+// In the JSON configuration, the clustering attributes are set directly in
+// the cache, but their mutable attribute names have this prefix.
+const CLUSTERING_PREFIX: &str = "clustering.";
+
+/// What [`ensure_cache`] did to make the cache match its configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reconciliation {
+    /// The cache did not exist and it was created.
+    Created,
+    /// The cache already had the expected configuration.
+    Unchanged,
+    /// The cache had different values for some mutable attributes, and they
+    /// were updated.
+    Updated(Vec<AttributeDiff>),
+}
+
+/// An attribute of the expected configuration that has a different value in
+/// the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeDiff {
+    /// Dotted path of the attribute, like "memory.max-count".
+    pub attribute: String,
+    pub expected: Value,
+    /// `None` when the server configuration does not have the attribute.
+    pub actual: Option<Value>,
+    /// Whether the attribute can be changed while the cache is running.
+    pub mutable: bool,
+}
+
+impl fmt::Display for AttributeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, found ", self.attribute, self.expected)?;
+
+        match &self.actual {
+            Some(actual) => write!(f, "{}", actual)?,
+            None => write!(f, "nothing")?,
+        }
+
+        if self.mutable {
+            write!(f, " (mutable)")
+        } else {
+            write!(f, " (not mutable)")
+        }
+    }
+}
+
+/// Makes sure that the cache exists with the given configuration:
+///
+/// - If the cache does not exist, it is created. Indexed caches are checked
+///   with [`check_indexed_entities`] first.
+/// - If it exists with an equivalent configuration, nothing is done.
+/// - If the configurations only differ in mutable attributes, those are
+///   updated in the running cache.
+/// - Otherwise, it fails with [`InfinispanError::CacheConfigMismatch`], that
+///   includes the attributes that differ.
+///
+/// The configurations are compared with the `?action=compare` operation of
+/// the server, so that attributes with default values are handled correctly.
+pub async fn ensure_cache(
+    client: &Infinispan,
+    name: impl AsRef<str>,
+    cache: &Cache,
+) -> Result<Reconciliation, InfinispanError> {
+    reconcile_cache(client, name.as_ref(), cache, None).await
+}
+
+/// Like [`ensure_cache`], but the indexed entities are checked against
+/// `schemas`, the content returned by [`registered_schemas`], instead of
+/// fetching the schemas from the server. Useful to ensure several caches with
+/// a single fetch.
+pub async fn ensure_cache_with_schemas(
+    client: &Infinispan,
+    name: impl AsRef<str>,
+    cache: &Cache,
+    schemas: &[String],
+) -> Result<Reconciliation, InfinispanError> {
+    reconcile_cache(client, name.as_ref(), cache, Some(schemas)).await
+}
+
+async fn reconcile_cache(
+    client: &Infinispan,
+    name: &str,
+    cache: &Cache,
+    schemas: Option<&[String]>,
+) -> Result<Reconciliation, InfinispanError> {
+    cache.validate()?;
+
+    let exists_req = caches::exists(name);
+    let resp = client.run(&exists_req).await?;
+
+    if resp.status() == StatusCode::NOT_FOUND {
+        match (enabled_indexing(cache), schemas) {
+            (Some(indexing), Some(schemas)) => indexing.check_entities(schemas)?,
+            (Some(_), None) => check_indexed_entities(client, cache).await?,
+            (None, _) => {}
+        }
+
+        client
+            .run_typed(&caches::create(name, cache.clone()))
+            .await?;
+
+        return Ok(Reconciliation::Created);
+    }
+
+    exists_req.parse_response(resp.status(), "")?;
+
+    let actual = client.run_typed(&caches::get_config(name)).await?;
+
+    let equivalent = client
+        .run_typed(&caches::compare(cache.clone(), actual.clone()))
+        .await?;
+
+    if equivalent {
+        return Ok(Reconciliation::Unchanged);
+    }
+
+    let mutable_attributes = client
+        .run_typed(&caches::get_mutable_attributes(name))
+        .await?;
+
+    let diffs = diff(cache, &actual, &mutable_attributes);
+
+    let equivalent_ignoring_mutable = client
+        .run_typed(&caches::compare(cache.clone(), actual).ignore_mutable())
+        .await?;
+
+    // When the server reports differences that the diff cannot find, there is
+    // nothing that can be updated.
+    if !equivalent_ignoring_mutable || diffs.is_empty() || diffs.iter().any(|diff| !diff.mutable) {
+        return Err(InfinispanError::CacheConfigMismatch {
+            name: name.into(),
+            diffs,
+        });
+    }
+
+    for diff in &diffs {
+        let value = match &diff.expected {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+
+        let attribute_name = if mutable_attributes.contains(&diff.attribute) {
+            diff.attribute.clone()
+        } else {
+            format!("{}{}", CLUSTERING_PREFIX, diff.attribute)
+        };
+
+        client
+            .run_typed(&caches::set_mutable_attribute(name, attribute_name, value))
+            .await?;
+    }
+
+    Ok(Reconciliation::Updated(diffs))
+}
+
+/// Checks that the entities indexed by the cache are messages declared in the
+/// Protobuf schemas registered in the server. The server fails to start an
+/// indexed cache otherwise. [`ensure_cache`] runs it before creating a cache.
+pub async fn check_indexed_entities(
+    client: &Infinispan,
+    cache: &Cache,
+) -> Result<(), InfinispanError> {
+    match enabled_indexing(cache) {
+        Some(indexing) => indexing.check_entities(&registered_schemas(client).await?),
+        None => Ok(()),
+    }
+}
+
+fn enabled_indexing(cache: &Cache) -> Option<&Indexing> {
+    cache.indexing().filter(|indexing| indexing.is_enabled())
+}
+
+/// Returns the content of the Protobuf schemas registered in the server, to
+/// be used with [`ensure_cache_with_schemas`].
+pub async fn registered_schemas(client: &Infinispan) -> Result<Vec<String>, InfinispanError> {
+    let mut schemas = Vec::new();
+
+    for name in registered_schema_names(client).await? {
+        schemas.push(run_text(client, &schemas::get(name)).await?);
+    }
+
+    Ok(schemas)
+}
+
+// Depending on the version, the server lists the schemas by name, or as
+// objects with the name and the validation errors.
+async fn registered_schema_names(client: &Infinispan) -> Result<Vec<String>, InfinispanError> {
+    let list: Vec<Value> = serde_json::from_str(&run_text(client, &schemas::list()).await?)?;
+
+    Ok(list
+        .into_iter()
+        .filter_map(|schema| match schema {
+            Value::String(name) => Some(name),
+            Value::Object(mut schema) => match schema.remove("name") {
+                Some(Value::String(name)) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect())
+}
+
+async fn run_text(client: &Infinispan, request: &Request) -> Result<String, InfinispanError> {
+    let resp = client.run(request).await?;
+    let status = resp.status();
+    let body = resp.text_with_charset("utf-8").await?;
+
+    check_status(status, &body)?;
+    Ok(body)
+}
+
+/// Returns the attributes set in `expected` that have a different value in
+/// `actual`. Attributes that are only in `actual`, usually defaults added by
+/// the server, are ignored.
+pub fn diff(expected: &Cache, actual: &Cache, mutable_attributes: &[String]) -> Vec<AttributeDiff> {
+    let (expected_type, expected) = cache_type_and_attributes(expected);
+    let (actual_type, actual) = cache_type_and_attributes(actual);
+
+    if expected_type != actual_type {
+        return vec![AttributeDiff {
+            attribute: "type".into(),
+            expected: expected_type.into(),
+            actual: Some(actual_type.into()),
+            mutable: false,
+        }];
+    }
+
+    diff_json(&expected, &actual, mutable_attributes)
+}
+
+/// Same as [`diff`], but for any JSON configuration.
+pub(crate) fn diff_json(
+    expected: &Value,
+    actual: &Value,
+    mutable_attributes: &[String],
+) -> Vec<AttributeDiff> {
+    let mut diffs = Vec::new();
+    diff_values("", expected, Some(actual), mutable_attributes, &mut diffs);
+    diffs
+}
+
+fn cache_type_and_attributes(cache: &Cache) -> (String, Value) {
+    match json!(cache) {
+        Value::Object(map) => map
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| (String::new(), Value::Null)),
+        _ => (String::new(), Value::Null),
+    }
+}
+
+fn diff_values(
+    path: &str,
+    expected: &Value,
+    actual: Option<&Value>,
+    mutable_attributes: &[String],
+    diffs: &mut Vec<AttributeDiff>,
+) {
+    match (expected, actual) {
+        (Value::Object(expected), None | Some(Value::Object(_))) => {
+            for (name, expected_val) in expected {
+                let attribute = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+
+                diff_values(
+                    &attribute,
+                    expected_val,
+                    actual.and_then(|actual| actual.get(name)),
+                    mutable_attributes,
+                    diffs,
+                );
+            }
+        }
+        (expected, actual) => {
+            if !actual.is_some_and(|actual| same_value(path, expected, actual)) {
+                diffs.push(AttributeDiff {
+                    attribute: path.into(),
+                    expected: expected.clone(),
+                    actual: actual.cloned(),
+                    mutable: is_mutable(path, mutable_attributes),
+                });
+            }
+        }
+    }
+}
+
+fn same_value(attribute: &str, expected: &Value, actual: &Value) -> bool {
+    expected == actual || normalize(attribute, expected) == normalize(attribute, actual)
+}
+
+// The server can return numbers and booleans as strings, and memory sizes as
+// bytes, so values are compared in a common form: memory sizes in bytes and
+// numbers as floats.
+fn normalize(attribute: &str, value: &Value) -> Value {
+    let is_memory_size = attribute.rsplit('.').next() == Some("max-size");
+
+    match value {
+        Value::String(value) if is_memory_size => match value.parse::<MemorySize>() {
+            Ok(size) => Value::from(size.bytes() as f64),
+            Err(_) => Value::String(value.clone()),
+        },
+        Value::String(value) => match value.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            trimmed => match trimmed.parse::<f64>() {
+                Ok(number) if number.is_finite() => Value::from(number),
+                _ => Value::String(value.clone()),
+            },
+        },
+        Value::Number(number) => number.as_f64().map_or(Value::Null, Value::from),
+        value => value.clone(),
+    }
+}
+
+fn is_mutable(attribute: &str, mutable_attributes: &[String]) -> bool {
+    mutable_attributes.iter().any(|mutable_attribute| {
+        mutable_attribute == attribute
+            || mutable_attribute.strip_prefix(CLUSTERING_PREFIX) == Some(attribute)
+    })
+}
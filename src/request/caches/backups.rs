@@ -0,0 +1,155 @@
+use crate::request::caches::cache_url;
+use crate::request::{Method, Request};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+enum Action {
+    TakeOffline,
+    BringOnline,
+    StartPushState,
+    CancelPushState,
+    PushStateStatus,
+    ClearPushStateStatus,
+}
+
+impl Action {
+    pub fn to_query_args(&self) -> String {
+        let action = match self {
+            Action::TakeOffline => "take-offline",
+            Action::BringOnline => "bring-online",
+            Action::StartPushState => "start-push-state",
+            Action::CancelPushState => "cancel-push-state",
+            Action::PushStateStatus => "push-state-status",
+            Action::ClearPushStateStatus => "clear-push-state-status",
+        };
+
+        format!("action={}", action)
+    }
+}
+
+/// Status of all the backup locations of the cache.
+pub fn status(cache_name: impl AsRef<str>) -> Request {
+    Request::new(Method::Get, backups_url(cache_name), HashMap::new(), None)
+}
+
+pub fn site_status(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Get,
+        site_url(cache_name, site_name),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn take_offline(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        site_url_with_action(cache_name, site_name, &Action::TakeOffline),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn bring_online(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        site_url_with_action(cache_name, site_name, &Action::BringOnline),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// Starts transferring the state of the cache to the site.
+pub fn push_state(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        site_url_with_action(cache_name, site_name, &Action::StartPushState),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn cancel_push_state(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        site_url_with_action(cache_name, site_name, &Action::CancelPushState),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// Status of the state transfers to all the backup locations of the cache.
+pub fn push_state_status(cache_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Get,
+        backups_url_with_action(cache_name, &Action::PushStateStatus),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn clear_push_state_status(cache_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        backups_url_with_action(cache_name, &Action::ClearPushStateStatus),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn get_take_offline_config(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Get,
+        take_offline_config_url(cache_name, site_name),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// `min_wait` is in milliseconds.
+pub fn set_take_offline_config(
+    cache_name: impl AsRef<str>,
+    site_name: impl AsRef<str>,
+    after_failures: i32,
+    min_wait: i64,
+) -> Request {
+    Request::new(
+        Method::Put,
+        take_offline_config_url(cache_name, site_name),
+        HashMap::new(),
+        Some(json!({ "after_failures": after_failures, "min_wait": min_wait }).to_string()),
+    )
+}
+
+fn backups_url(cache_name: impl AsRef<str>) -> String {
+    format!("{}/x-site/backups", cache_url(cache_name))
+}
+
+fn backups_url_with_action(cache_name: impl AsRef<str>, action: &Action) -> String {
+    format!("{}?{}", backups_url(cache_name), action.to_query_args())
+}
+
+fn site_url(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> String {
+    format!(
+        "{}/{}",
+        backups_url(cache_name),
+        urlencoding::encode(site_name.as_ref())
+    )
+}
+
+fn site_url_with_action(
+    cache_name: impl AsRef<str>,
+    site_name: impl AsRef<str>,
+    action: &Action,
+) -> String {
+    format!(
+        "{}?{}",
+        site_url(cache_name, site_name),
+        action.to_query_args()
+    )
+}
+
+fn take_offline_config_url(cache_name: impl AsRef<str>, site_name: impl AsRef<str>) -> String {
+    format!("{}/take-offline-config", site_url(cache_name, site_name))
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Data of a cache stored in a node, returned by
+/// [`distribution`](super::distribution).
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDistribution {
+    pub node_name: String,
+    #[serde(default)]
+    pub node_addresses: Vec<String>,
+    /// Entries in memory in the node.
+    pub memory_entries: i64,
+    /// Entries in the node, including the ones only in persistent storage.
+    pub total_entries: i64,
+    /// Bytes used by the entries. Only calculated with size-based eviction.
+    pub memory_used: i64,
+}
+
+/// Nodes that own a key, returned by
+/// [`key_distribution`](super::key_distribution).
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct KeyDistribution {
+    /// Whether the cache contains the key. The owners are returned anyway.
+    pub contains_key: bool,
+    pub owners: Vec<KeyOwner>,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct KeyOwner {
+    pub node_name: String,
+    /// Whether the node is the primary owner of the key.
+    pub primary: bool,
+    #[serde(default)]
+    pub node_addresses: Vec<String>,
+}
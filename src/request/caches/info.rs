@@ -0,0 +1,110 @@
+use crate::request::caches::{Cache, CacheStats};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Clustering mode of a cache, as reported in [`CacheInfo`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CacheMode {
+    Local,
+    DistSync,
+    DistAsync,
+    ReplSync,
+    ReplAsync,
+    InvalidationSync,
+    InvalidationAsync,
+    ScatteredSync,
+    /// A mode that this client does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Information about a cache, returned by [`get`](super::get).
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CacheInfo {
+    pub stats: CacheStats,
+    /// Number of entries. It is -1 when the size cannot be calculated.
+    pub size: i64,
+    pub configuration: Cache,
+    pub mode: CacheMode,
+    #[serde(default)]
+    pub rehash_in_progress: bool,
+    #[serde(default)]
+    pub indexing_in_progress: bool,
+    /// Whether the cache has eviction.
+    #[serde(default)]
+    pub bounded: bool,
+    #[serde(default)]
+    pub indexed: bool,
+    #[serde(default)]
+    pub persistent: bool,
+    #[serde(default)]
+    pub transactional: bool,
+    #[serde(default)]
+    pub secured: bool,
+    #[serde(default)]
+    pub has_remote_backup: bool,
+    /// Media type used to store the keys, like "application/x-protostream".
+    #[serde(default)]
+    pub key_storage: String,
+    #[serde(default)]
+    pub value_storage: String,
+    /// Attributes that are not modeled, like the query statistics.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CacheStatus {
+    Instantiated,
+    Running,
+    Stopping,
+    Terminated,
+    Failed,
+    /// A status that this client does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CacheHealth {
+    Healthy,
+    HealthyRebalancing,
+    Degraded,
+    Failed,
+    /// A health status that this client does not know.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A cache in the list returned by [`list_detailed`](super::list_detailed).
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSummary {
+    pub name: String,
+    pub status: CacheStatus,
+    /// Type of the cache configuration, like "distributed-cache".
+    #[serde(rename = "type")]
+    pub cache_type: String,
+    pub health: CacheHealth,
+    #[serde(default)]
+    pub simple_cache: bool,
+    #[serde(default)]
+    pub transactional: bool,
+    #[serde(default)]
+    pub persistent: bool,
+    #[serde(default)]
+    pub bounded: bool,
+    #[serde(default)]
+    pub secured: bool,
+    #[serde(default)]
+    pub indexed: bool,
+    #[serde(default)]
+    pub has_remote_backup: bool,
+    #[serde(default)]
+    pub rebalancing_enabled: bool,
+    /// Attributes that are not modeled.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
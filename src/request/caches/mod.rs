@@ -1,45 +1,412 @@
+pub mod backups;
+mod distribution;
+mod info;
 pub mod modes;
+mod stats;
 
+pub use distribution::{KeyDistribution, KeyOwner, NodeDistribution};
+pub use info::{CacheHealth, CacheInfo, CacheMode, CacheStatus, CacheSummary};
+pub use stats::CacheStats;
+
+use crate::errors::InfinispanError;
 use crate::request::caches::modes::*;
-use crate::request::{Method, Request};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
+use crate::request::{check_status, JsonRequest, Method, ParseResponse, Request, ToHttpRequest};
+use http::header::CONTENT_TYPE;
+use http::StatusCode;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
 use std::collections::HashMap;
 
 pub(crate) const CACHES_ENDPOINT: &str = "/rest/v2/caches";
 
-#[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+const CONTENT_TYPE_JSON: &str = "application/json";
+const CONTENT_TYPE_XML: &str = "application/xml";
+const CONTENT_TYPE_YAML: &str = "application/yaml";
+
+const MULTIPART_BOUNDARY: &str = "infinispan-rs-config-boundary";
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize)]
 pub enum Cache {
-    #[serde(rename = "local-cache")]
+    #[serde(rename = "local-cache", alias = "local-cache-configuration")]
     Local(Local),
 
-    #[serde(rename = "replicated-cache")]
+    #[serde(rename = "replicated-cache", alias = "replicated-cache-configuration")]
     Replicated(Replicated),
 
-    #[serde(rename = "distributed-cache")]
+    #[serde(
+        rename = "distributed-cache",
+        alias = "distributed-cache-configuration"
+    )]
     Distributed(Distributed),
 
-    #[serde(rename = "invalidation-cache")]
+    #[serde(
+        rename = "invalidation-cache",
+        alias = "invalidation-cache-configuration"
+    )]
     Invalidation(Invalidation),
+
+    /// A mode that this client does not model, like the scattered caches of
+    /// Infinispan 11 to 14. It keeps the whole configuration, including the
+    /// name of the mode.
+    #[serde(untagged)]
+    Other(Value),
+}
+
+// Only the modes that are not modeled are kept as `Other`, so that an error in
+// the configuration of a known mode is reported instead of being ignored.
+impl<'de> Deserialize<'de> for Cache {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+
+        let (mode, config) = match value.as_object().filter(|modes| modes.len() == 1) {
+            Some(modes) => modes.iter().next().unwrap(),
+            None => return Ok(Cache::Other(value)),
+        };
+
+        let cache = match mode.as_str() {
+            "local-cache" | "local-cache-configuration" => {
+                Local::deserialize(config).map(Cache::Local)
+            }
+            "replicated-cache" | "replicated-cache-configuration" => {
+                Replicated::deserialize(config).map(Cache::Replicated)
+            }
+            "distributed-cache" | "distributed-cache-configuration" => {
+                Distributed::deserialize(config).map(Cache::Distributed)
+            }
+            "invalidation-cache" | "invalidation-cache-configuration" => {
+                Invalidation::deserialize(config).map(Cache::Invalidation)
+            }
+            _ => return Ok(Cache::Other(value)),
+        };
+
+        cache.map_err(|err| D::Error::custom(format!("{}: {}", mode, err)))
+    }
+}
+
+impl Cache {
+    /// Checks for combinations of attributes that Infinispan would reject.
+    pub fn validate(&self) -> Result<(), InfinispanError> {
+        match self {
+            Cache::Local(local) => local.validate(),
+            Cache::Replicated(replicated) => replicated.validate(),
+            Cache::Distributed(distributed) => distributed.validate(),
+            Cache::Invalidation(invalidation) => invalidation.validate(),
+            // Left to the server.
+            Cache::Other(_) => Ok(()),
+        }
+    }
+
+    /// The `indexing` section of the configuration, if it has one.
+    pub fn indexing(&self) -> Option<&Indexing> {
+        match self {
+            Cache::Local(local) => local.indexing(),
+            Cache::Replicated(replicated) => replicated.indexing(),
+            Cache::Distributed(distributed) => distributed.indexing(),
+            Cache::Invalidation(invalidation) => invalidation.indexing(),
+            Cache::Other(_) => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateCacheReq {
+    name: String,
+    cache: Cache,
+}
+
+impl CreateCacheReq {
+    pub fn new(name: impl Into<String>, cache: Cache) -> Self {
+        Self {
+            name: name.into(),
+            cache,
+        }
+    }
+}
+
+impl From<&CreateCacheReq> for Request {
+    fn from(request: &CreateCacheReq) -> Self {
+        Self::new(
+            Method::Post,
+            cache_url(&request.name),
+            HashMap::new(),
+            Some(json!(request.cache).to_string()),
+        )
+    }
+}
+
+impl ToHttpRequest for CreateCacheReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        self.cache.validate()
+    }
+}
+
+impl ParseResponse for CreateCacheReq {
+    type Output = ();
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+        check_status(status, body)
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateCacheReq {
+    name: String,
+    cache: Cache,
+}
+
+impl UpdateCacheReq {
+    pub fn new(name: impl Into<String>, cache: Cache) -> Self {
+        Self {
+            name: name.into(),
+            cache,
+        }
+    }
+}
+
+impl From<&UpdateCacheReq> for Request {
+    fn from(request: &UpdateCacheReq) -> Self {
+        Self::new(
+            Method::Put,
+            cache_url(&request.name),
+            HashMap::new(),
+            Some(json!(request.cache).to_string()),
+        )
+    }
+}
+
+impl ToHttpRequest for UpdateCacheReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        self.cache.validate()
+    }
+}
+
+impl ParseResponse for UpdateCacheReq {
+    type Output = ();
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+        check_status(status, body)
+    }
+}
+
+/// Compares two cache configurations in the server, which knows the default
+/// value of every attribute.
+#[derive(Debug)]
+pub struct CompareConfigReq {
+    config: Cache,
+    other: Cache,
+    ignore_mutable: bool,
+}
+
+impl CompareConfigReq {
+    pub fn new(config: Cache, other: Cache) -> Self {
+        Self {
+            config,
+            other,
+            ignore_mutable: false,
+        }
+    }
+
+    /// Only compares the attributes that cannot be changed on a running cache.
+    pub fn ignore_mutable(mut self) -> Self {
+        self.ignore_mutable = true;
+        self
+    }
+}
+
+impl From<&CompareConfigReq> for Request {
+    fn from(request: &CompareConfigReq) -> Self {
+        let mut path_and_query = format!("{}?action=compare", CACHES_ENDPOINT);
+
+        if request.ignore_mutable {
+            path_and_query.push_str("&ignoreMutable=true");
+        }
+
+        let mut headers = HashMap::new();
+        headers.insert(
+            CONTENT_TYPE.to_string(),
+            format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY),
+        );
+
+        let mut body = String::new();
+
+        for (part_name, config) in [("one", &request.config), ("two", &request.other)] {
+            body.push_str(&format!(
+                "--{boundary}\r\n\
+                 Content-Disposition: form-data; name=\"{part_name}\"\r\n\
+                 Content-Type: {content_type}\r\n\r\n\
+                 {config}\r\n",
+                boundary = MULTIPART_BOUNDARY,
+                part_name = part_name,
+                content_type = CONTENT_TYPE_JSON,
+                config = json!(config),
+            ));
+        }
+
+        body.push_str(&format!("--{}--\r\n", MULTIPART_BOUNDARY));
+
+        Self::new(Method::Post, path_and_query, headers, Some(body))
+    }
+}
+
+impl ToHttpRequest for CompareConfigReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+}
+
+impl ParseResponse for CompareConfigReq {
+    /// Whether the configurations are equivalent.
+    type Output = bool;
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<bool, InfinispanError> {
+        match status {
+            StatusCode::CONFLICT => Ok(false),
+            _ => check_status(status, body).map(|_| true),
+        }
+    }
+}
+
+/// Whether a cache serves reads and writes. Caches enter the degraded mode
+/// during network partitions, depending on their `partition-handling`.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Availability {
+    Available,
+    DegradedMode,
+}
+
+impl Availability {
+    pub const fn as_str(&self) -> &str {
+        match self {
+            Availability::Available => "AVAILABLE",
+            Availability::DegradedMode => "DEGRADED_MODE",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GetAvailabilityReq {
+    name: String,
+}
+
+impl GetAvailabilityReq {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl From<&GetAvailabilityReq> for Request {
+    fn from(request: &GetAvailabilityReq) -> Self {
+        Self::new(
+            Method::Get,
+            cache_url_with_action(&request.name, &Action::GetAvailability),
+            HashMap::new(),
+            None,
+        )
+    }
+}
+
+impl ToHttpRequest for GetAvailabilityReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+}
+
+impl ParseResponse for GetAvailabilityReq {
+    type Output = Availability;
+
+    // The server returns the availability as plain text, like "AVAILABLE".
+    fn parse_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) -> Result<Availability, InfinispanError> {
+        check_status(status, body)?;
+        Ok(serde_json::from_value(Value::String(body.trim().into()))?)
+    }
 }
 
 #[derive(Debug)]
 enum Action {
     Clear,
     Config,
+    Detailed,
+    DisableRebalancing,
+    Distribution,
+    EnableRebalancing,
+    KeyDistribution { key: String },
     Keys,
     Size,
     Stats,
+    StatsReset,
+    GetAvailability,
+    SetAvailability(Availability),
+    GetMutableAttributes,
+    SetMutableAttribute { name: String, value: String },
 }
 
 impl Action {
     pub fn to_query_args(&self) -> String {
-        let mut query_args = format!("action={:?}", self);
-        query_args.make_ascii_lowercase();
-        query_args
+        match self {
+            Action::Clear => "action=clear".to_string(),
+            Action::Config => "action=config".to_string(),
+            Action::Detailed => "action=detailed".to_string(),
+            Action::DisableRebalancing => "action=disable-rebalancing".to_string(),
+            Action::Distribution => "action=distribution".to_string(),
+            Action::EnableRebalancing => "action=enable-rebalancing".to_string(),
+            Action::KeyDistribution { key } => {
+                format!("action=distribution&key={}", urlencoding::encode(key))
+            }
+            Action::Keys => "action=keys".to_string(),
+            Action::Size => "action=size".to_string(),
+            Action::Stats => "action=stats".to_string(),
+            Action::StatsReset => "action=stats-reset".to_string(),
+            Action::GetAvailability => "action=get-availability".to_string(),
+            Action::SetAvailability(availability) => {
+                format!(
+                    "action=set-availability&availability={}",
+                    availability.as_str()
+                )
+            }
+            Action::GetMutableAttributes => "action=get-mutable-attributes".to_string(),
+            Action::SetMutableAttribute { name, value } => format!(
+                "action=set-mutable-attribute&attribute-name={}&attribute-value={}",
+                urlencoding::encode(name),
+                urlencoding::encode(value)
+            ),
+        }
     }
 }
 
+pub fn create(name: impl Into<String>, cache: Cache) -> CreateCacheReq {
+    CreateCacheReq::new(name, cache)
+}
+
 pub fn create_local(name: impl AsRef<str>) -> Request {
     create_cache(name, Cache::Local(Local::default()))
 }
@@ -68,23 +435,158 @@ pub fn create_invalidation_sync(name: impl AsRef<str>) -> Request {
     create_cache(name, Cache::Invalidation(Invalidation::create_sync()))
 }
 
+/// Creates a cache from its configuration in the Infinispan XML format.
+pub fn create_from_xml(name: impl AsRef<str>, xml: impl Into<String>) -> Request {
+    create_cache_from_str(name, xml, CONTENT_TYPE_XML)
+}
+
+/// Creates a cache from its configuration in the Infinispan YAML format.
+pub fn create_from_yaml(name: impl AsRef<str>, yaml: impl Into<String>) -> Request {
+    create_cache_from_str(name, yaml, CONTENT_TYPE_YAML)
+}
+
+/// Creates a cache from its configuration in the Infinispan JSON format.
+pub fn create_from_json(name: impl AsRef<str>, json: impl Into<String>) -> Request {
+    create_cache_from_str(name, json, CONTENT_TYPE_JSON)
+}
+
+/// Creates a cache from a configuration template, like
+/// "org.infinispan.DIST_SYNC". See [`templates`](crate::request::templates)
+/// to get the ones available.
+pub fn create_from_template(name: impl AsRef<str>, template: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        format!(
+            "{}?template={}",
+            cache_url(name),
+            urlencoding::encode(template.as_ref())
+        ),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn compare(config: Cache, other: Cache) -> CompareConfigReq {
+    CompareConfigReq::new(config, other)
+}
+
 pub fn exists(name: impl AsRef<str>) -> Request {
     Request::new(Method::Head, cache_url(name), HashMap::new(), None)
 }
 
-pub fn get(name: impl AsRef<str>) -> Request {
-    Request::new(Method::Get, cache_url(name), HashMap::new(), None)
+pub fn get(name: impl AsRef<str>) -> JsonRequest<CacheInfo> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        cache_url(name),
+        HashMap::new(),
+        None,
+    ))
 }
 
-pub fn get_config(name: impl AsRef<str>) -> Request {
-    Request::new(
+pub fn get_config(name: impl AsRef<str>) -> JsonRequest<Cache> {
+    JsonRequest::new(Request::new(
         Method::Get,
         cache_url_with_action(name, &Action::Config),
         HashMap::new(),
         None,
+    ))
+}
+
+/// Replaces the configuration of an existing cache. The server rejects the
+/// update if it changes attributes that are not mutable.
+pub fn update_config(name: impl Into<String>, cache: Cache) -> UpdateCacheReq {
+    UpdateCacheReq::new(name, cache)
+}
+
+/// Names of the attributes that can be changed while the cache is running,
+/// like "expiration.lifespan" or "memory.max-count".
+pub fn get_mutable_attributes(name: impl AsRef<str>) -> JsonRequest<Vec<String>> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        cache_url_with_action(name, &Action::GetMutableAttributes),
+        HashMap::new(),
+        None,
+    ))
+}
+
+pub fn set_mutable_attribute(
+    name: impl AsRef<str>,
+    attribute_name: impl Into<String>,
+    attribute_value: impl ToString,
+) -> Request {
+    let action = Action::SetMutableAttribute {
+        name: attribute_name.into(),
+        value: attribute_value.to_string(),
+    };
+
+    Request::new(
+        Method::Post,
+        cache_url_with_action(name, &action),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn get_availability(name: impl Into<String>) -> GetAvailabilityReq {
+    GetAvailabilityReq::new(name)
+}
+
+/// Changes the availability of a cache, for example, to make it available
+/// again after a network partition was solved.
+pub fn set_availability(name: impl AsRef<str>, availability: Availability) -> Request {
+    Request::new(
+        Method::Post,
+        cache_url_with_action(name, &Action::SetAvailability(availability)),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn enable_rebalancing(name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        cache_url_with_action(name, &Action::EnableRebalancing),
+        HashMap::new(),
+        None,
     )
 }
 
+/// Stops moving entries between nodes when the cluster topology changes, for
+/// example, during a maintenance window.
+pub fn disable_rebalancing(name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        cache_url_with_action(name, &Action::DisableRebalancing),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// Returns the number of entries and the memory used in each node.
+pub fn distribution(name: impl AsRef<str>) -> JsonRequest<Vec<NodeDistribution>> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        cache_url_with_action(name, &Action::Distribution),
+        HashMap::new(),
+        None,
+    ))
+}
+
+/// Returns the nodes that own a key.
+pub fn key_distribution(
+    name: impl AsRef<str>,
+    key: impl Into<String>,
+) -> JsonRequest<KeyDistribution> {
+    let action = Action::KeyDistribution { key: key.into() };
+
+    JsonRequest::new(Request::new(
+        Method::Get,
+        cache_url_with_action(name, &action),
+        HashMap::new(),
+        None,
+    ))
+}
+
 pub fn delete(name: impl AsRef<str>) -> Request {
     Request::new(Method::Delete, cache_url(name), HashMap::new(), None)
 }
@@ -116,12 +618,22 @@ pub fn size(name: impl AsRef<str>) -> Request {
     )
 }
 
-pub fn stats(name: impl AsRef<str>) -> Request {
-    Request::new(
+pub fn stats(name: impl AsRef<str>) -> JsonRequest<CacheStats> {
+    JsonRequest::new(Request::new(
         Method::Get,
         cache_url_with_action(name, &Action::Stats),
         HashMap::new(),
         None,
+    ))
+}
+
+/// Resets the statistics of the cache, like hits and misses.
+pub fn reset_stats(name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        cache_url_with_action(name, &Action::StatsReset),
+        HashMap::new(),
+        None,
     )
 }
 
@@ -129,7 +641,17 @@ pub fn list() -> Request {
     Request::new(Method::Get, CACHES_ENDPOINT, HashMap::new(), None)
 }
 
-fn cache_url(name: impl AsRef<str>) -> String {
+/// Lists the caches with their status, type and health.
+pub fn list_detailed() -> JsonRequest<Vec<CacheSummary>> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        format!("{}?{}", CACHES_ENDPOINT, Action::Detailed.to_query_args()),
+        HashMap::new(),
+        None,
+    ))
+}
+
+pub(crate) fn cache_url(name: impl AsRef<str>) -> String {
     format!(
         "{caches_endpoint}/{cache_name}",
         caches_endpoint = CACHES_ENDPOINT,
@@ -149,3 +671,14 @@ fn create_cache(name: impl AsRef<str>, cache: Cache) -> Request {
         Some(json!(cache).to_string()),
     )
 }
+
+fn create_cache_from_str(
+    name: impl AsRef<str>,
+    config: impl Into<String>,
+    content_type: &str,
+) -> Request {
+    let mut headers = HashMap::new();
+    headers.insert(CONTENT_TYPE.to_string(), content_type.to_string());
+
+    Request::new(Method::Post, cache_url(name), headers, Some(config.into()))
+}
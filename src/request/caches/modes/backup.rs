@@ -0,0 +1,229 @@
+use crate::errors::InfinispanError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BackupStrategy {
+    Sync,
+    Async,
+}
+
+/// What to do when a write cannot be backed up to a site.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BackupFailurePolicy {
+    Ignore,
+    Warn,
+    Fail,
+}
+
+/// When to stop backing up to a site that keeps failing.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TakeOffline {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after_failures: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_wait: Option<i64>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl TakeOffline {
+    pub fn with_after_failures(mut self, after_failures: i32) -> Self {
+        self.after_failures = Some(after_failures);
+        self
+    }
+
+    /// Minimum time in milliseconds to wait before taking the site offline.
+    pub fn with_min_wait(mut self, min_wait: i64) -> Self {
+        self.min_wait = Some(min_wait);
+        self
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Backup {
+    strategy: BackupStrategy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure_policy: Option<BackupFailurePolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    take_offline: Option<TakeOffline>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Backup {
+    pub fn new(strategy: BackupStrategy) -> Self {
+        Self {
+            strategy,
+            failure_policy: None,
+            timeout: None,
+            take_offline: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_failure_policy(mut self, failure_policy: BackupFailurePolicy) -> Self {
+        self.failure_policy = Some(failure_policy);
+        self
+    }
+
+    /// Timeout in milliseconds for the backup requests.
+    pub fn with_timeout(mut self, timeout: i64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_take_offline(mut self, take_offline: TakeOffline) -> Self {
+        self.take_offline = Some(take_offline);
+        self
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSite {
+    backup: Backup,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+/// The `backups` section of a cache configuration: the sites where the cache
+/// is backed up, indexed by site name, and the attributes shared by all the
+/// sites.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawBackups", into = "RawBackups")]
+pub struct Backups {
+    sites: BTreeMap<String, BackupSite>,
+    merge_policy: Option<String>,
+    max_cleanup_delay: Option<i64>,
+    tombstone_map_size: Option<i32>,
+    other: Map<String, Value>,
+}
+
+// Sites and attributes share the same keys in the JSON configuration. Sites
+// are the objects with a "backup" key.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawBackups {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    merge_policy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_cleanup_delay: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tombstone_map_size: Option<i32>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl TryFrom<RawBackups> for Backups {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawBackups) -> Result<Self, Self::Error> {
+        let mut sites = BTreeMap::new();
+        let mut other = Map::new();
+
+        for (key, value) in raw.other {
+            if value.get("backup").is_some() {
+                sites.insert(key, serde_json::from_value(value)?);
+            } else {
+                other.insert(key, value);
+            }
+        }
+
+        Ok(Self {
+            sites,
+            merge_policy: raw.merge_policy,
+            max_cleanup_delay: raw.max_cleanup_delay,
+            tombstone_map_size: raw.tombstone_map_size,
+            other,
+        })
+    }
+}
+
+impl From<Backups> for RawBackups {
+    fn from(backups: Backups) -> Self {
+        let mut other = backups.other;
+
+        for (site, backup_site) in backups.sites {
+            // BackupSite only contains maps and strings, so it always
+            // serializes.
+            other.insert(site, serde_json::to_value(backup_site).unwrap());
+        }
+
+        Self {
+            merge_policy: backups.merge_policy,
+            max_cleanup_delay: backups.max_cleanup_delay,
+            tombstone_map_size: backups.tombstone_map_size,
+            other,
+        }
+    }
+}
+
+impl Backups {
+    pub fn with_site(mut self, site: impl Into<String>, backup: Backup) -> Self {
+        self.sites.insert(
+            site.into(),
+            BackupSite {
+                backup,
+                other: Map::new(),
+            },
+        );
+        self
+    }
+
+    /// How conflicts between sites are resolved, like "DEFAULT",
+    /// "PREFER_NON_NULL" or the class name of a custom policy.
+    pub fn with_merge_policy(mut self, merge_policy: impl Into<String>) -> Self {
+        self.merge_policy = Some(merge_policy.into());
+        self
+    }
+
+    /// Maximum time in milliseconds between cleanups of the tombstones of
+    /// removed entries.
+    pub fn with_max_cleanup_delay(mut self, max_cleanup_delay: i64) -> Self {
+        self.max_cleanup_delay = Some(max_cleanup_delay);
+        self
+    }
+
+    pub fn with_tombstone_map_size(mut self, tombstone_map_size: i32) -> Self {
+        self.tombstone_map_size = Some(tombstone_map_size);
+        self
+    }
+
+    pub fn merge_policy(&self) -> Option<&str> {
+        self.merge_policy.as_deref()
+    }
+
+    pub fn max_cleanup_delay(&self) -> Option<i64> {
+        self.max_cleanup_delay
+    }
+
+    pub fn tombstone_map_size(&self) -> Option<i32> {
+        self.tombstone_map_size
+    }
+
+    pub fn site(&self, site: impl AsRef<str>) -> Option<&Backup> {
+        self.sites.get(site.as_ref()).map(|site| &site.backup)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        for (site, BackupSite { backup, .. }) in &self.sites {
+            if backup.strategy == BackupStrategy::Async
+                && backup.failure_policy == Some(BackupFailurePolicy::Fail)
+            {
+                return Err(InfinispanError::InvalidConfig(format!(
+                    "backups: site {} uses the FAIL failure policy, which requires the SYNC strategy",
+                    site
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
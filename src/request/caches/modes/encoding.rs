@@ -0,0 +1,122 @@
+use crate::errors::InfinispanError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const MEDIA_TYPE_PROTOSTREAM: &str = "application/x-protostream";
+pub const MEDIA_TYPE_JAVA_OBJECT: &str = "application/x-java-object";
+pub const MEDIA_TYPE_JSON: &str = "application/json";
+pub const MEDIA_TYPE_OCTET_STREAM: &str = "application/octet-stream";
+pub const MEDIA_TYPE_TEXT_PLAIN: &str = "text/plain";
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EncodingMediaType {
+    media_type: String,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+/// The `encoding` section of a cache configuration. It sets the media types
+/// used to store keys and values, which must match what other clients of the
+/// cache (Hot Rod, for example) expect.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Encoding {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<EncodingMediaType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<EncodingMediaType>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Encoding {
+    /// Uses the same media type for keys and values.
+    pub fn new(media_type: impl Into<String>) -> Self {
+        Self {
+            media_type: Some(media_type.into()),
+            key: None,
+            value: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn protostream() -> Self {
+        Self::new(MEDIA_TYPE_PROTOSTREAM)
+    }
+
+    pub fn with_key(mut self, media_type: impl Into<String>) -> Self {
+        self.key = Some(EncodingMediaType {
+            media_type: media_type.into(),
+            other: Map::new(),
+        });
+        self
+    }
+
+    pub fn with_value(mut self, media_type: impl Into<String>) -> Self {
+        self.value = Some(EncodingMediaType {
+            media_type: media_type.into(),
+            other: Map::new(),
+        });
+        self
+    }
+
+    pub fn key_media_type(&self) -> Option<&str> {
+        self.key
+            .as_ref()
+            .map(|key| key.media_type.as_str())
+            .or(self.media_type.as_deref())
+    }
+
+    pub fn value_media_type(&self) -> Option<&str> {
+        self.value
+            .as_ref()
+            .map(|value| value.media_type.as_str())
+            .or(self.media_type.as_deref())
+    }
+
+    /// Whether both keys and values are stored in a binary format, as
+    /// opposed to Java objects.
+    pub fn is_binary(&self) -> bool {
+        let is_binary = |media_type: Option<&str>| {
+            media_type.is_some_and(|m| !essence(m).eq_ignore_ascii_case(MEDIA_TYPE_JAVA_OBJECT))
+        };
+
+        is_binary(self.key_media_type()) && is_binary(self.value_media_type())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        let media_types = [
+            self.media_type.as_deref(),
+            self.key.as_ref().map(|key| key.media_type.as_str()),
+            self.value.as_ref().map(|value| value.media_type.as_str()),
+        ];
+
+        for media_type in media_types.iter().flatten() {
+            if !is_valid_media_type(media_type) {
+                return Err(InfinispanError::InvalidConfig(format!(
+                    "encoding: {:?} is not a valid media type",
+                    media_type
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The media type without its parameters, like "; charset=UTF-8".
+fn essence(media_type: &str) -> &str {
+    media_type.split(';').next().unwrap_or_default().trim()
+}
+
+fn is_valid_media_type(media_type: &str) -> bool {
+    match essence(media_type).split_once('/') {
+        Some((type_, subtype)) => {
+            !type_.is_empty() && !subtype.is_empty() && !subtype.contains('/')
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,234 @@
+use crate::errors::InfinispanError;
+use crate::request::caches::modes::{Encoding, MEDIA_TYPE_PROTOSTREAM};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexStorage {
+    Filesystem,
+    LocalHeap,
+}
+
+/// The `indexing` section of a cache configuration.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Indexing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<IndexStorage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    indexed_entities: Vec<String>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Indexing {
+    /// Enables indexing for the given Protobuf message types, like
+    /// "sample.Person".
+    pub fn new<I, S>(indexed_entities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            enabled: Some(true),
+            indexed_entities: indexed_entities.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    pub fn with_storage(mut self, storage: IndexStorage) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Directory of the indexes, with `Filesystem` storage.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_indexed_entity(mut self, entity: impl Into<String>) -> Self {
+        self.indexed_entities.push(entity.into());
+        self
+    }
+
+    pub fn indexed_entities(&self) -> &[String] {
+        &self.indexed_entities
+    }
+
+    /// The server enables indexing when there are indexed entities, unless
+    /// it is explicitly disabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(!self.indexed_entities.is_empty())
+    }
+
+    /// Checks that the indexed entities are messages declared in the given
+    /// Protobuf schemas. The server fails to start the cache otherwise. To
+    /// check against the schemas registered in the server, use
+    /// [`check_indexed_entities`](crate::reconcile::check_indexed_entities).
+    pub fn check_entities<S: AsRef<str>>(&self, schemas: &[S]) -> Result<(), InfinispanError> {
+        let declared: Vec<String> = schemas
+            .iter()
+            .flat_map(|schema| proto_message_names(schema.as_ref()))
+            .collect();
+
+        for entity in &self.indexed_entities {
+            if !declared.contains(entity) {
+                return Err(InfinispanError::InvalidConfig(format!(
+                    "indexing: {} is not declared in the schemas",
+                    entity
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate(&self, encoding: Option<&Encoding>) -> Result<(), InfinispanError> {
+        if self.path.is_some() && self.storage == Some(IndexStorage::LocalHeap) {
+            return Err(InfinispanError::InvalidConfig(
+                "indexing: a path can only be set with filesystem storage".into(),
+            ));
+        }
+
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        if self.indexed_entities.is_empty() {
+            return Err(InfinispanError::InvalidConfig(
+                "indexing: at least one indexed entity is required".into(),
+            ));
+        }
+
+        for entity in &self.indexed_entities {
+            if !is_message_name(entity) {
+                return Err(InfinispanError::InvalidConfig(format!(
+                    "indexing: {:?} is not a valid Protobuf message name",
+                    entity
+                )));
+            }
+        }
+
+        // Indexed entities are Protobuf messages, so the values must be
+        // stored as Protobuf.
+        let is_protostream = encoding
+            .and_then(Encoding::value_media_type)
+            .is_some_and(|media_type| media_type.starts_with(MEDIA_TYPE_PROTOSTREAM));
+
+        if !is_protostream {
+            return Err(InfinispanError::InvalidConfig(format!(
+                "indexing: indexed caches must store values as {}",
+                MEDIA_TYPE_PROTOSTREAM
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn is_message_name(name: &str) -> bool {
+    name.split('.').all(|segment| {
+        let mut chars = segment.chars();
+
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+// Full names of the messages declared in a schema, including the nested
+// ones, like "sample.Person" and "sample.Person.Address".
+fn proto_message_names(schema: &str) -> Vec<String> {
+    let without_comments = strip_comments(schema);
+    let tokens: Vec<&str> = without_comments
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .flat_map(split_braces)
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    let mut package = None;
+    let mut scopes: Vec<Option<&str>> = Vec::new();
+    let mut pending_message = None;
+    let mut names = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i] {
+            "package" if scopes.is_empty() => {
+                package = tokens.get(i + 1).copied();
+                i += 1;
+            }
+            "message" => {
+                if let Some(name) = tokens.get(i + 1) {
+                    let full_name = package
+                        .into_iter()
+                        .chain(scopes.iter().flatten().copied())
+                        .chain(Some(*name))
+                        .collect::<Vec<_>>()
+                        .join(".");
+
+                    names.push(full_name);
+                    pending_message = Some(*name);
+                }
+                i += 1;
+            }
+            "{" => scopes.push(pending_message.take()),
+            "}" => {
+                scopes.pop();
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    names
+}
+
+fn split_braces(token: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in token.char_indices() {
+        if c == '{' || c == '}' {
+            parts.push(&token[start..i]);
+            parts.push(&token[i..i + 1]);
+            start = i + 1;
+        }
+    }
+
+    parts.push(&token[start..]);
+    parts
+}
+
+fn strip_comments(schema: &str) -> String {
+    let mut result = String::with_capacity(schema.len());
+    let mut rest = schema;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |end| &after[end..]);
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map_or("", |end| &after[end + 2..]);
+            result.push(' ');
+        } else {
+            let c = rest.chars().next().unwrap();
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    result
+}
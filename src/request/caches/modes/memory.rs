@@ -0,0 +1,214 @@
+use crate::errors::InfinispanError;
+use crate::request::caches::modes::Encoding;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::str::FromStr;
+
+const KB: u64 = 1_000;
+const KIB: u64 = 1_024;
+
+// Ordered from largest to smallest so that formatting picks the biggest unit
+// that represents the size exactly.
+const UNITS: [(&str, u64); 8] = [
+    ("TiB", KIB * KIB * KIB * KIB),
+    ("TB", KB * KB * KB * KB),
+    ("GiB", KIB * KIB * KIB),
+    ("GB", KB * KB * KB),
+    ("MiB", KIB * KIB),
+    ("MB", KB * KB),
+    ("KiB", KIB),
+    ("KB", KB),
+];
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StorageType {
+    Heap,
+    OffHeap,
+}
+
+/// What the cache does when it reaches its `max-count` or `max-size`.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EvictionStrategy {
+    /// Evicts entries automatically.
+    Remove,
+    /// Rejects writes that would add new entries.
+    Exception,
+    /// Eviction is left to the application.
+    Manual,
+}
+
+/// An amount of memory, as accepted by the `max-size` attribute.
+///
+/// It can be parsed from strings like "100MB", "1.5GB" or "512KiB". Decimal
+/// units (KB, MB, GB, TB) are powers of 1000, binary units (KiB, MiB, GiB,
+/// TiB) are powers of 1024, and a plain number is a number of bytes.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct MemorySize {
+    bytes: u64,
+}
+
+impl MemorySize {
+    pub const fn from_bytes(bytes: u64) -> Self {
+        Self { bytes }
+    }
+
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl FromStr for MemorySize {
+    type Err = InfinispanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InfinispanError::InvalidConfig(format!("invalid memory size: {:?}", s));
+
+        let s = s.trim();
+        let unit_start = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (amount, unit) = s.split_at(unit_start);
+
+        let multiplier = match unit.trim() {
+            "" | "B" => 1,
+            unit => {
+                UNITS
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+                    .ok_or_else(invalid)?
+                    .1
+            }
+        };
+
+        if let Ok(amount) = amount.parse::<u64>() {
+            return amount
+                .checked_mul(multiplier)
+                .map(Self::from_bytes)
+                .ok_or_else(invalid);
+        }
+
+        let amount = amount.parse::<f64>().map_err(|_| invalid())?;
+        let bytes = (amount * multiplier as f64).round();
+
+        if bytes.is_finite() && bytes >= 0.0 && bytes <= u64::MAX as f64 {
+            Ok(Self::from_bytes(bytes as u64))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl fmt::Display for MemorySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = UNITS
+            .iter()
+            .find(|(_, multiplier)| self.bytes != 0 && self.bytes.is_multiple_of(*multiplier));
+
+        match unit {
+            Some((name, multiplier)) => write!(f, "{}{}", self.bytes / multiplier, name),
+            None => write!(f, "{}", self.bytes),
+        }
+    }
+}
+
+impl Serialize for MemorySize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MemorySize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MemorySizeVisitor;
+
+        impl<'de> Visitor<'de> for MemorySizeVisitor {
+            type Value = MemorySize;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a memory size like \"100MB\" or a number of bytes")
+            }
+
+            fn visit_u64<E: de::Error>(self, bytes: u64) -> Result<Self::Value, E> {
+                Ok(MemorySize::from_bytes(bytes))
+            }
+
+            fn visit_i64<E: de::Error>(self, bytes: i64) -> Result<Self::Value, E> {
+                u64::try_from(bytes)
+                    .map(MemorySize::from_bytes)
+                    .map_err(|_| E::custom("memory size cannot be negative"))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(MemorySizeVisitor)
+    }
+}
+
+/// The `memory` section of a cache configuration. It controls where entries
+/// are stored and how the cache is bounded.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Memory {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<StorageType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_size: Option<MemorySize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    when_full: Option<EvictionStrategy>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Memory {
+    pub fn with_storage(mut self, storage: StorageType) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    pub fn with_max_count(mut self, max_count: i64) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: MemorySize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn with_when_full(mut self, when_full: EvictionStrategy) -> Self {
+        self.when_full = Some(when_full);
+        self
+    }
+
+    pub(crate) fn validate(&self, encoding: Option<&Encoding>) -> Result<(), InfinispanError> {
+        if self.max_count.is_some() && self.max_size.is_some() {
+            return Err(InfinispanError::InvalidConfig(
+                "memory: max-count and max-size cannot be set at the same time".into(),
+            ));
+        }
+
+        // Size-based eviction needs to know the size of the entries, so they
+        // must be stored in binary form. That is always the case off-heap, but
+        // on the heap it depends on the encoding.
+        let is_binary =
+            self.storage == Some(StorageType::OffHeap) || encoding.is_some_and(Encoding::is_binary);
+
+        if self.max_size.is_some() && !is_binary {
+            return Err(InfinispanError::InvalidConfig(
+                "memory: max-size requires a binary encoding for keys and values, or OFF_HEAP storage"
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+mod backup;
+mod encoding;
+mod indexing;
+mod memory;
+mod partition_handling;
+mod persistence;
+mod transaction;
+
+pub use backup::*;
+pub use encoding::*;
+pub use indexing::*;
+pub use memory::*;
+pub use partition_handling::*;
+pub use persistence::*;
+pub use transaction::*;
+
+use crate::errors::InfinispanError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const DEFAULT_CONCURRENCY_LEVEL: i32 = 1_000;
+const DEFAULT_ACQUIRE_TIMEOUT: i32 = 15_000;
+const DEFAULT_STATE_TRANSFER_TIMEOUT: i32 = 60_000;
+const DEFAULT_REMOTE_TIMEOUT: i32 = 17_500;
+
+// Builders, getters and validation of the attributes shared by every cache
+// mode. The modes need the `locking`, `memory`, `encoding`, `persistence`,
+// `transaction`, `indexing` and `other` fields.
+macro_rules! impl_common_attributes {
+    ($mode:ident) => {
+        impl $mode {
+            pub fn with_memory(mut self, memory: Memory) -> Self {
+                self.memory = Some(memory);
+                self
+            }
+
+            pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+                self.encoding = Some(encoding);
+                self
+            }
+
+            pub fn with_persistence(mut self, persistence: Persistence) -> Self {
+                self.persistence = Some(persistence);
+                self
+            }
+
+            pub fn with_transaction(mut self, transaction: Transaction) -> Self {
+                self.transaction = Some(transaction);
+                self
+            }
+
+            pub fn with_isolation(mut self, isolation: IsolationLevel) -> Self {
+                self.locking.get_or_insert_with(Locking::default).isolation = Some(isolation);
+                self
+            }
+
+            pub fn with_indexing(mut self, indexing: Indexing) -> Self {
+                self.indexing = Some(indexing);
+                self
+            }
+
+            pub fn indexing(&self) -> Option<&Indexing> {
+                self.indexing.as_ref()
+            }
+
+            /// Attributes returned by the server that this crate does not
+            /// model. They are kept so that a configuration can be read,
+            /// modified and sent back without losing them.
+            pub fn other_attributes(&self) -> &Map<String, Value> {
+                &self.other
+            }
+
+            pub fn with_other_attribute(mut self, name: impl Into<String>, value: Value) -> Self {
+                self.other.insert(name.into(), value);
+                self
+            }
+
+            fn validate_common(&self) -> Result<(), InfinispanError> {
+                if let Some(encoding) = &self.encoding {
+                    encoding.validate()?;
+                }
+
+                if let Some(memory) = &self.memory {
+                    memory.validate(self.encoding.as_ref())?;
+                }
+
+                if let Some(persistence) = &self.persistence {
+                    persistence.validate()?;
+                }
+
+                if let Some(transaction) = &self.transaction {
+                    transaction.validate()?;
+                }
+
+                if let Some(indexing) = &self.indexing {
+                    indexing.validate(self.encoding.as_ref())?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+// Builders of the cross-site attributes of clustered caches that keep a copy
+// of every entry. The modes need the `backups` and `partition_handling`
+// fields.
+macro_rules! impl_cross_site_attributes {
+    ($mode:ident) => {
+        impl $mode {
+            pub fn with_backups(mut self, backups: Backups) -> Self {
+                self.backups = Some(backups);
+                self
+            }
+
+            pub fn with_partition_handling(
+                mut self,
+                partition_handling: PartitionHandling,
+            ) -> Self {
+                self.partition_handling = Some(partition_handling);
+                self
+            }
+        }
+    };
+}
+
+impl_common_attributes!(Local);
+impl_common_attributes!(Distributed);
+impl_common_attributes!(Replicated);
+impl_common_attributes!(Invalidation);
+impl_cross_site_attributes!(Distributed);
+impl_cross_site_attributes!(Replicated);
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Local {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locking: Option<Locking>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<Memory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<Encoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistence: Option<Persistence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction: Option<Transaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    indexing: Option<Indexing>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Local {
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        self.validate_common()
+    }
+}
+
+impl Default for Local {
+    fn default() -> Self {
+        Self {
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Distributed {
+    mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_transfer: Option<StateTransfer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locking: Option<Locking>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<Memory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<Encoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistence: Option<Persistence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction: Option<Transaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backups: Option<Backups>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition_handling: Option<PartitionHandling>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    indexing: Option<Indexing>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Distributed {
+    pub fn create_async() -> Self {
+        Self {
+            mode: "ASYNC".into(),
+            state_transfer: Some(StateTransfer::default()),
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            backups: None,
+            partition_handling: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn create_sync() -> Self {
+        Self {
+            mode: "SYNC".into(),
+            state_transfer: Some(StateTransfer::default()),
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            backups: None,
+            partition_handling: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        self.validate_common()?;
+
+        if let Some(backups) = &self.backups {
+            backups.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Replicated {
+    mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_timeout: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_transfer: Option<StateTransfer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locking: Option<Locking>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<Memory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<Encoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistence: Option<Persistence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction: Option<Transaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backups: Option<Backups>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition_handling: Option<PartitionHandling>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    indexing: Option<Indexing>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Replicated {
+    pub fn create_async() -> Self {
+        Self {
+            mode: "ASYNC".into(),
+            remote_timeout: None,
+            state_transfer: Some(StateTransfer::default()),
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            backups: None,
+            partition_handling: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn create_sync() -> Self {
+        Self {
+            mode: "SYNC".into(),
+            remote_timeout: Some(DEFAULT_REMOTE_TIMEOUT),
+            state_transfer: Some(StateTransfer::default()),
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            backups: None,
+            partition_handling: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        self.validate_common()?;
+
+        if let Some(backups) = &self.backups {
+            backups.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Invalidation {
+    mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_timeout: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locking: Option<Locking>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<Memory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<Encoding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistence: Option<Persistence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction: Option<Transaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    indexing: Option<Indexing>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Invalidation {
+    pub fn create_async() -> Self {
+        Self {
+            mode: "ASYNC".into(),
+            remote_timeout: None,
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn create_sync() -> Self {
+        Self {
+            mode: "SYNC".into(),
+            remote_timeout: Some(DEFAULT_REMOTE_TIMEOUT),
+            locking: Some(Locking::default()),
+            statistics: Some(true),
+            memory: None,
+            encoding: None,
+            persistence: None,
+            transaction: None,
+            indexing: None,
+            other: Map::new(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        self.validate_common()?;
+
+        if let Some(transaction) = &self.transaction {
+            if transaction.is_transactional() && self.mode == "ASYNC" {
+                return Err(InfinispanError::InvalidConfig(
+                    "transaction: invalidation caches in ASYNC mode cannot be transactional".into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Locking {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    concurrency_level: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    acquire_timeout: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    striping: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    isolation: Option<IsolationLevel>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Default for Locking {
+    fn default() -> Self {
+        Self {
+            concurrency_level: Some(DEFAULT_CONCURRENCY_LEVEL),
+            acquire_timeout: Some(DEFAULT_ACQUIRE_TIMEOUT),
+            striping: Some(false),
+            isolation: None,
+            other: Map::new(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+struct StateTransfer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<i32>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Default for StateTransfer {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_STATE_TRANSFER_TIMEOUT),
+            other: Map::new(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// What a cache does with reads and writes during a network partition.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WhenSplit {
+    /// Keep serving reads and writes, even if they can be inconsistent.
+    AllowReadWrites,
+    /// Only serve reads of keys whose owners are all in the partition.
+    AllowReads,
+    /// Only serve reads and writes of keys whose owners are all in the
+    /// partition.
+    DenyReadWrites,
+}
+
+/// How conflicting entries are resolved when the partitions merge.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum MergePolicy {
+    None,
+    PreferredAlways,
+    PreferredNonNull,
+    RemoveAll,
+    /// Fully qualified name of a custom `EntryMergePolicy` class.
+    Custom(String),
+}
+
+impl From<String> for MergePolicy {
+    fn from(merge_policy: String) -> Self {
+        match merge_policy.as_str() {
+            "NONE" => MergePolicy::None,
+            "PREFERRED_ALWAYS" => MergePolicy::PreferredAlways,
+            "PREFERRED_NON_NULL" => MergePolicy::PreferredNonNull,
+            "REMOVE_ALL" => MergePolicy::RemoveAll,
+            _ => MergePolicy::Custom(merge_policy),
+        }
+    }
+}
+
+impl From<MergePolicy> for String {
+    fn from(merge_policy: MergePolicy) -> Self {
+        match merge_policy {
+            MergePolicy::None => "NONE".into(),
+            MergePolicy::PreferredAlways => "PREFERRED_ALWAYS".into(),
+            MergePolicy::PreferredNonNull => "PREFERRED_NON_NULL".into(),
+            MergePolicy::RemoveAll => "REMOVE_ALL".into(),
+            MergePolicy::Custom(class_name) => class_name,
+        }
+    }
+}
+
+/// The `partition-handling` section of a cache configuration.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionHandling {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    when_split: Option<WhenSplit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_policy: Option<MergePolicy>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl PartitionHandling {
+    pub fn with_when_split(mut self, when_split: WhenSplit) -> Self {
+        self.when_split = Some(when_split);
+        self
+    }
+
+    pub fn with_merge_policy(mut self, merge_policy: MergePolicy) -> Self {
+        self.merge_policy = Some(merge_policy);
+        self
+    }
+}
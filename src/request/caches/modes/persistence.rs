@@ -0,0 +1,518 @@
+use crate::errors::InfinispanError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const DEFAULT_REMOTE_SERVER_PORT: u16 = 11_222;
+
+/// Attributes shared by all the persistent stores.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoreAttributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shared: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preload: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segmented: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    write_behind: Option<WriteBehind>,
+}
+
+impl StoreAttributes {
+    pub fn is_shared(&self) -> bool {
+        self.shared.unwrap_or(false)
+    }
+}
+
+/// Writes to the store asynchronously instead of on every cache write.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WriteBehind {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modification_queue_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fail_silently: Option<bool>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl WriteBehind {
+    pub fn with_modification_queue_size(mut self, size: u32) -> Self {
+        self.modification_queue_size = Some(size);
+        self
+    }
+
+    pub fn with_fail_silently(mut self, fail_silently: bool) -> Self {
+        self.fail_silently = Some(fail_silently);
+        self
+    }
+}
+
+/// Builder methods for the attributes shared by all the stores.
+pub trait Store: Sized {
+    fn attributes(&self) -> &StoreAttributes;
+
+    fn attributes_mut(&mut self) -> &mut StoreAttributes;
+
+    /// Marks the store as shared by all the nodes of the cluster.
+    fn with_shared(mut self, shared: bool) -> Self {
+        self.attributes_mut().shared = Some(shared);
+        self
+    }
+
+    /// Loads the data of the store into memory when the cache starts.
+    fn with_preload(mut self, preload: bool) -> Self {
+        self.attributes_mut().preload = Some(preload);
+        self
+    }
+
+    /// Clears the store when the cache starts.
+    fn with_purge(mut self, purge: bool) -> Self {
+        self.attributes_mut().purge = Some(purge);
+        self
+    }
+
+    fn with_read_only(mut self, read_only: bool) -> Self {
+        self.attributes_mut().read_only = Some(read_only);
+        self
+    }
+
+    fn with_segmented(mut self, segmented: bool) -> Self {
+        self.attributes_mut().segmented = Some(segmented);
+        self
+    }
+
+    fn with_write_behind(mut self, write_behind: WriteBehind) -> Self {
+        self.attributes_mut().write_behind = Some(write_behind);
+        self
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct StorePath {
+    path: String,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+/// Soft-index file store. It keeps the data in files in the local file
+/// system of each node.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FileStore {
+    #[serde(flatten)]
+    attributes: StoreAttributes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<StorePath>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<StorePath>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl FileStore {
+    pub fn with_data_path(mut self, path: impl Into<String>) -> Self {
+        self.data = Some(StorePath {
+            path: path.into(),
+            other: Map::new(),
+        });
+        self
+    }
+
+    pub fn with_index_path(mut self, path: impl Into<String>) -> Self {
+        self.index = Some(StorePath {
+            path: path.into(),
+            other: Map::new(),
+        });
+        self
+    }
+}
+
+impl Store for FileStore {
+    fn attributes(&self) -> &StoreAttributes {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut StoreAttributes {
+        &mut self.attributes
+    }
+}
+
+/// JDBC connection pool managed by the store itself.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConnectionPool {
+    connection_url: String,
+    driver: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl ConnectionPool {
+    pub fn new(connection_url: impl Into<String>, driver: impl Into<String>) -> Self {
+        Self {
+            connection_url: connection_url.into(),
+            driver: driver.into(),
+            username: None,
+            password: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+}
+
+/// Data source defined in the server configuration.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DataSource {
+    jndi_url: String,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct TableColumn {
+    name: String,
+    #[serde(rename = "type")]
+    column_type: String,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl TableColumn {
+    pub fn new(name: impl Into<String>, column_type: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            column_type: column_type.into(),
+            other: Map::new(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StringKeyedTable {
+    prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    create_on_start: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drop_on_exit: Option<bool>,
+    id_column: TableColumn,
+    data_column: TableColumn,
+    timestamp_column: TableColumn,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segment_column: Option<TableColumn>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl StringKeyedTable {
+    pub fn new(
+        prefix: impl Into<String>,
+        id_column: TableColumn,
+        data_column: TableColumn,
+        timestamp_column: TableColumn,
+    ) -> Self {
+        Self {
+            prefix: prefix.into(),
+            create_on_start: None,
+            drop_on_exit: None,
+            id_column,
+            data_column,
+            timestamp_column,
+            segment_column: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_create_on_start(mut self, create_on_start: bool) -> Self {
+        self.create_on_start = Some(create_on_start);
+        self
+    }
+
+    pub fn with_drop_on_exit(mut self, drop_on_exit: bool) -> Self {
+        self.drop_on_exit = Some(drop_on_exit);
+        self
+    }
+
+    pub fn with_segment_column(mut self, segment_column: TableColumn) -> Self {
+        self.segment_column = Some(segment_column);
+        self
+    }
+}
+
+/// JDBC store that keeps each entry in a row of a table, with the key
+/// converted to a string.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JdbcStore {
+    #[serde(flatten)]
+    attributes: StoreAttributes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dialect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connection_pool: Option<ConnectionPool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_source: Option<DataSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    string_keyed_table: Option<StringKeyedTable>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl JdbcStore {
+    /// `dialect` is the name of the database dialect, like "POSTGRES" or "H2".
+    pub fn with_dialect(mut self, dialect: impl Into<String>) -> Self {
+        self.dialect = Some(dialect.into());
+        self
+    }
+
+    pub fn with_connection_pool(mut self, connection_pool: ConnectionPool) -> Self {
+        self.connection_pool = Some(connection_pool);
+        self
+    }
+
+    pub fn with_data_source(mut self, jndi_url: impl Into<String>) -> Self {
+        self.data_source = Some(DataSource {
+            jndi_url: jndi_url.into(),
+            other: Map::new(),
+        });
+        self
+    }
+
+    pub fn with_table(mut self, table: StringKeyedTable) -> Self {
+        self.string_keyed_table = Some(table);
+        self
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        match (&self.connection_pool, &self.data_source) {
+            (Some(_), Some(_)) => Err(InfinispanError::InvalidConfig(
+                "persistence: a JDBC store cannot have both a connection pool and a data source"
+                    .into(),
+            )),
+            (None, None) => Err(InfinispanError::InvalidConfig(
+                "persistence: a JDBC store needs a connection pool or a data source".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Store for JdbcStore {
+    fn attributes(&self) -> &StoreAttributes {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut StoreAttributes {
+        &mut self.attributes
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RocksDbStore {
+    #[serde(flatten)]
+    attributes: StoreAttributes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiration: Option<StorePath>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl RocksDbStore {
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_expiration_path(mut self, path: impl Into<String>) -> Self {
+        self.expiration = Some(StorePath {
+            path: path.into(),
+            other: Map::new(),
+        });
+        self
+    }
+}
+
+impl Store for RocksDbStore {
+    fn attributes(&self) -> &StoreAttributes {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut StoreAttributes {
+        &mut self.attributes
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteServer {
+    host: String,
+    port: u16,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl RemoteServer {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: DEFAULT_REMOTE_SERVER_PORT,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+/// Store backed by a cache in another Infinispan cluster.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteStore {
+    #[serde(flatten)]
+    attributes: StoreAttributes,
+    cache: String,
+    #[serde(default)]
+    remote_server: Vec<RemoteServer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_values: Option<bool>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl RemoteStore {
+    pub fn new(cache: impl Into<String>) -> Self {
+        Self {
+            attributes: StoreAttributes::default(),
+            cache: cache.into(),
+            remote_server: Vec::new(),
+            raw_values: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_server(mut self, server: RemoteServer) -> Self {
+        self.remote_server.push(server);
+        self
+    }
+
+    pub fn with_raw_values(mut self, raw_values: bool) -> Self {
+        self.raw_values = Some(raw_values);
+        self
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        if self.remote_server.is_empty() {
+            return Err(InfinispanError::InvalidConfig(
+                "persistence: a remote store needs at least one remote server".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Store for RemoteStore {
+    fn attributes(&self) -> &StoreAttributes {
+        &self.attributes
+    }
+
+    fn attributes_mut(&mut self) -> &mut StoreAttributes {
+        &mut self.attributes
+    }
+}
+
+/// The `persistence` section of a cache configuration. It configures the
+/// stores where the cache writes its entries so that they survive restarts.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Persistence {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    passivation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_store: Option<FileStore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    string_keyed_jdbc_store: Option<JdbcStore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rocksdb_store: Option<RocksDbStore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_store: Option<RemoteStore>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Persistence {
+    /// With passivation, entries are only written to the store when they are
+    /// evicted from memory.
+    pub fn with_passivation(mut self, passivation: bool) -> Self {
+        self.passivation = Some(passivation);
+        self
+    }
+
+    pub fn with_file_store(mut self, store: FileStore) -> Self {
+        self.file_store = Some(store);
+        self
+    }
+
+    pub fn with_jdbc_store(mut self, store: JdbcStore) -> Self {
+        self.string_keyed_jdbc_store = Some(store);
+        self
+    }
+
+    pub fn with_rocksdb_store(mut self, store: RocksDbStore) -> Self {
+        self.rocksdb_store = Some(store);
+        self
+    }
+
+    pub fn with_remote_store(mut self, store: RemoteStore) -> Self {
+        self.remote_store = Some(store);
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        if let Some(store) = &self.string_keyed_jdbc_store {
+            store.validate()?;
+        }
+
+        if let Some(store) = &self.remote_store {
+            store.validate()?;
+        }
+
+        let stores = [
+            self.file_store.as_ref().map(Store::attributes),
+            self.string_keyed_jdbc_store.as_ref().map(Store::attributes),
+            self.rocksdb_store.as_ref().map(Store::attributes),
+            self.remote_store.as_ref().map(Store::attributes),
+        ];
+
+        let passivation = self.passivation.unwrap_or(false);
+
+        if passivation && stores.iter().flatten().any(|store| store.is_shared()) {
+            return Err(InfinispanError::InvalidConfig(
+                "persistence: passivation cannot be used with shared stores".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
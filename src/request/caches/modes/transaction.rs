@@ -0,0 +1,98 @@
+use crate::errors::InfinispanError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionMode {
+    None,
+    Batch,
+    NonXa,
+    NonDurableXa,
+    FullXa,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LockingMode {
+    Optimistic,
+    Pessimistic,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IsolationLevel {
+    None,
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// The `transaction` section of a cache configuration.
+#[derive(Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Transaction {
+    mode: TransactionMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locking: Option<LockingMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_timeout: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    complete_timeout: Option<i64>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Transaction {
+    pub fn new(mode: TransactionMode) -> Self {
+        Self {
+            mode,
+            locking: None,
+            auto_commit: None,
+            stop_timeout: None,
+            complete_timeout: None,
+            other: Map::new(),
+        }
+    }
+
+    pub fn with_locking(mut self, locking: LockingMode) -> Self {
+        self.locking = Some(locking);
+        self
+    }
+
+    pub fn with_auto_commit(mut self, auto_commit: bool) -> Self {
+        self.auto_commit = Some(auto_commit);
+        self
+    }
+
+    /// Time in milliseconds to wait for ongoing transactions when the cache
+    /// stops.
+    pub fn with_stop_timeout(mut self, stop_timeout: i64) -> Self {
+        self.stop_timeout = Some(stop_timeout);
+        self
+    }
+
+    /// Time in milliseconds to keep the information about completed
+    /// transactions.
+    pub fn with_complete_timeout(mut self, complete_timeout: i64) -> Self {
+        self.complete_timeout = Some(complete_timeout);
+        self
+    }
+
+    pub fn is_transactional(&self) -> bool {
+        self.mode != TransactionMode::None
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), InfinispanError> {
+        if !self.is_transactional() && self.locking.is_some() {
+            return Err(InfinispanError::InvalidConfig(
+                "transaction: a locking mode can only be set on transactional caches".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
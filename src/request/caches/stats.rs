@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Statistics of a cache, returned by [`stats`](super::stats).
+///
+/// Most counters are -1 when statistics are not enabled in the cache. Missing
+/// attributes, like the ones that older servers do not return, are 0.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheStats {
+    /// Seconds since the cache started.
+    pub time_since_start: i64,
+    /// Seconds since the statistics were reset.
+    pub time_since_reset: i64,
+    pub current_number_of_entries: i64,
+    pub current_number_of_entries_in_memory: i64,
+    pub total_number_of_entries: i64,
+    /// Bytes used by the entries. Only calculated with size-based eviction.
+    pub data_memory_used: i64,
+    pub off_heap_memory_used: i64,
+    pub stores: i64,
+    pub retrievals: i64,
+    pub hits: i64,
+    pub misses: i64,
+    pub remove_hits: i64,
+    pub remove_misses: i64,
+    pub evictions: i64,
+    /// Milliseconds.
+    pub average_read_time: i64,
+    pub average_read_time_nanos: i64,
+    /// Milliseconds.
+    pub average_write_time: i64,
+    pub average_write_time_nanos: i64,
+    /// Milliseconds.
+    pub average_remove_time: i64,
+    pub average_remove_time_nanos: i64,
+    pub required_minimum_number_of_nodes: i64,
+    /// Hits divided by retrievals, between 0 and 1.
+    pub hit_ratio: f64,
+    pub read_write_ratio: f64,
+}
+
+impl CacheStats {
+    /// Remove operations, whether the entry existed or not.
+    pub fn removes(&self) -> i64 {
+        self.remove_hits + self.remove_misses
+    }
+}
@@ -1,54 +1,324 @@
-use crate::request::{Method, Request, ToHttpRequest};
+use crate::errors::InfinispanError;
+use crate::request::{
+    check_status, parse_json_with, JsonRequest, Method, ParseResponse, Request, ToHttpRequest,
+};
+use crate::Infinispan;
+use http::StatusCode;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 const COUNTERS_ENDPOINT: &str = "/rest/v2/counters";
 
-type CounterVal = i64;
+pub type CounterVal = i64;
 
-#[derive(Debug, Serialize, Deserialize)]
-enum Counter {
+/// The configuration of a counter, returned by [`get_config`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Counter {
     #[serde(rename = "weak-counter")]
     Weak(WeakCounter),
     #[serde(rename = "strong-counter")]
     Strong(StrongCounter),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct WeakCounter {
-    #[serde(rename = "initial-value", skip_serializing_if = "Option::is_none")]
+impl Counter {
+    pub fn counter_type(&self) -> CounterType {
+        match self {
+            Counter::Weak(_) => CounterType::Weak,
+            Counter::Strong(_) => CounterType::Strong,
+        }
+    }
+
+    /// Only returned by the server.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Counter::Weak(counter) => counter.name(),
+            Counter::Strong(counter) => counter.name(),
+        }
+    }
+
+    pub fn initial_value(&self) -> Option<CounterVal> {
+        match self {
+            Counter::Weak(counter) => counter.initial_value(),
+            Counter::Strong(counter) => counter.initial_value(),
+        }
+    }
+
+    pub fn storage(&self) -> Option<CounterStorage> {
+        match self {
+            Counter::Weak(counter) => counter.storage(),
+            Counter::Strong(counter) => counter.storage(),
+        }
+    }
+}
+
+/// A counter that supports many concurrent updates, but whose value can only
+/// be read, and not used in conditional updates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WeakCounter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     initial_value: Option<CounterVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    storage: Option<CounterStorage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    concurrency_level: Option<u32>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
 }
 
 impl WeakCounter {
-    pub fn set_value(&mut self, counter_val: CounterVal) {
-        self.initial_value = Some(counter_val);
+    /// Only returned by the server.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn initial_value(&self) -> Option<CounterVal> {
+        self.initial_value
+    }
+
+    pub fn storage(&self) -> Option<CounterStorage> {
+        self.storage
+    }
+
+    pub fn concurrency_level(&self) -> Option<u32> {
+        self.concurrency_level
+    }
+
+    /// Attributes that are not modeled, like the ones of newer servers.
+    pub fn other_attributes(&self) -> &Map<String, Value> {
+        &self.other
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct StrongCounter {
-    #[serde(rename = "initial-value", skip_serializing_if = "Option::is_none")]
+/// A counter with an atomic value, that can have bounds and be updated
+/// conditionally.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StrongCounter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     initial_value: Option<CounterVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    storage: Option<CounterStorage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lower_bound: Option<CounterVal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upper_bound: Option<CounterVal>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
 }
 
 impl StrongCounter {
-    pub fn set_value(&mut self, counter_val: CounterVal) {
-        self.initial_value = Some(counter_val);
+    /// Only returned by the server.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn initial_value(&self) -> Option<CounterVal> {
+        self.initial_value
+    }
+
+    pub fn storage(&self) -> Option<CounterStorage> {
+        self.storage
+    }
+
+    pub fn lower_bound(&self) -> Option<CounterVal> {
+        self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> Option<CounterVal> {
+        self.upper_bound
+    }
+
+    /// Attributes that are not modeled, like the ones of newer servers.
+    pub fn other_attributes(&self) -> &Map<String, Value> {
+        &self.other
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CounterType {
     Weak,
     Strong,
 }
 
+/// Whether the value of a counter survives a restart of the cluster. By
+/// default, counters are volatile.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CounterStorage {
+    Volatile,
+    Persistent,
+}
+
+/// Creates a weak counter, returned by [`create_weak`].
+#[derive(Debug)]
+pub struct CreateWeakCounterReq {
+    name: String,
+    counter: WeakCounter,
+}
+
+impl CreateWeakCounterReq {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            counter: WeakCounter::default(),
+        }
+    }
+
+    /// The configuration of the counter that the request creates.
+    pub fn counter(&self) -> Counter {
+        Counter::Weak(self.counter.clone())
+    }
+
+    pub fn with_value(mut self, value: CounterVal) -> Self {
+        self.counter.initial_value = Some(value);
+        self
+    }
+
+    pub fn with_storage(mut self, storage: CounterStorage) -> Self {
+        self.counter.storage = Some(storage);
+        self
+    }
+
+    /// Number of concurrent updates that the counter supports. Higher values
+    /// make updates faster and reads slower.
+    pub fn with_concurrency_level(mut self, concurrency_level: u32) -> Self {
+        self.counter.concurrency_level = Some(concurrency_level);
+        self
+    }
+}
+
+impl ParseResponse for CreateWeakCounterReq {
+    type Output = ();
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+        check_status(status, body)
+    }
+}
+
+impl From<&CreateWeakCounterReq> for Request {
+    fn from(request: &CreateWeakCounterReq) -> Self {
+        create_counter_request(&request.name, &Counter::Weak(request.counter.clone()))
+    }
+}
+
+impl ToHttpRequest for CreateWeakCounterReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        if self.counter.concurrency_level == Some(0) {
+            return Err(InfinispanError::InvalidConfig(format!(
+                "counter {}: the concurrency level must be greater than 0",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a strong counter, returned by [`create_strong`].
 #[derive(Debug)]
-pub struct CreateCounterReq {
+pub struct CreateStrongCounterReq {
     name: String,
-    counter: Counter,
+    counter: StrongCounter,
+}
+
+impl CreateStrongCounterReq {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            counter: StrongCounter::default(),
+        }
+    }
+
+    /// The configuration of the counter that the request creates.
+    pub fn counter(&self) -> Counter {
+        Counter::Strong(self.counter.clone())
+    }
+
+    pub fn with_value(mut self, value: CounterVal) -> Self {
+        self.counter.initial_value = Some(value);
+        self
+    }
+
+    pub fn with_storage(mut self, storage: CounterStorage) -> Self {
+        self.counter.storage = Some(storage);
+        self
+    }
+
+    /// Lowest value of the counter. Updates that go below it fail.
+    pub fn with_lower_bound(mut self, lower_bound: CounterVal) -> Self {
+        self.counter.lower_bound = Some(lower_bound);
+        self
+    }
+
+    /// Highest value of the counter. Updates that go above it fail.
+    pub fn with_upper_bound(mut self, upper_bound: CounterVal) -> Self {
+        self.counter.upper_bound = Some(upper_bound);
+        self
+    }
+}
+
+impl ParseResponse for CreateStrongCounterReq {
+    type Output = ();
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+        check_status(status, body)
+    }
+}
+
+impl From<&CreateStrongCounterReq> for Request {
+    fn from(request: &CreateStrongCounterReq) -> Self {
+        create_counter_request(&request.name, &Counter::Strong(request.counter.clone()))
+    }
+}
+
+impl ToHttpRequest for CreateStrongCounterReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+
+    fn validate(&self) -> Result<(), InfinispanError> {
+        let lower_bound = self.counter.lower_bound.unwrap_or(CounterVal::MIN);
+        let upper_bound = self.counter.upper_bound.unwrap_or(CounterVal::MAX);
+
+        if lower_bound > upper_bound {
+            return Err(InfinispanError::InvalidConfig(format!(
+                "counter {}: the lower bound is greater than the upper bound",
+                self.name
+            )));
+        }
+
+        // The server uses 0 when the initial value is not set.
+        let initial_value = self.counter.initial_value.unwrap_or_default();
+
+        if !(lower_bound..=upper_bound).contains(&initial_value) {
+            return Err(InfinispanError::InvalidConfig(format!(
+                "counter {}: the initial value {} is not within the bounds",
+                self.name, initial_value
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -88,54 +358,9 @@ impl Action {
     }
 }
 
-impl CreateCounterReq {
-    pub fn new(name: impl Into<String>, counter_type: CounterType) -> Self {
-        let counter = match counter_type {
-            CounterType::Weak => Counter::Weak(WeakCounter {
-                initial_value: None,
-            }),
-            CounterType::Strong => Counter::Strong(StrongCounter {
-                initial_value: None,
-            }),
-        };
-
-        Self {
-            name: name.into(),
-            counter,
-        }
-    }
-
-    pub fn with_value(mut self, value: CounterVal) -> Self {
-        match &mut self.counter {
-            Counter::Weak(counter) => counter.set_value(value),
-            Counter::Strong(counter) => counter.set_value(value),
-        }
-
-        self
-    }
-}
-
-impl From<&CreateCounterReq> for Request {
-    fn from(request: &CreateCounterReq) -> Self {
-        Self::new(
-            Method::Post,
-            counter_path(&request.name),
-            HashMap::new(),
-            Some(json!(request.counter).to_string()),
-        )
-    }
-}
-
-impl ToHttpRequest for CreateCounterReq {
-    fn to_http_req(
-        &self,
-        base_url: impl AsRef<str>,
-        basic_auth_encoded: impl AsRef<str>,
-    ) -> http::Request<String> {
-        Request::from(self).to_http_req(base_url, basic_auth_encoded)
-    }
-}
-
+/// Adds to a counter, returned by [`increment`]. It returns the new value of
+/// strong counters, and `None` for weak counters, because the server does not
+/// return their value.
 #[derive(Debug)]
 pub struct IncrementCounterReq {
     name: String,
@@ -184,68 +409,119 @@ impl ToHttpRequest for IncrementCounterReq {
     }
 }
 
-pub fn create_weak(name: impl Into<String>) -> CreateCounterReq {
-    CreateCounterReq::new(name, CounterType::Weak)
+impl ParseResponse for IncrementCounterReq {
+    /// The new value of strong counters, or `None` for weak counters.
+    type Output = Option<CounterVal>;
+
+    fn parse_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) -> Result<Option<CounterVal>, InfinispanError> {
+        parse_json_with(check_counter_status, status, body)
+    }
+}
+
+pub fn create_weak(name: impl Into<String>) -> CreateWeakCounterReq {
+    CreateWeakCounterReq::new(name)
 }
 
-pub fn create_strong(name: impl Into<String>) -> CreateCounterReq {
-    CreateCounterReq::new(name, CounterType::Strong)
+pub fn create_strong(name: impl Into<String>) -> CreateStrongCounterReq {
+    CreateStrongCounterReq::new(name)
 }
 
-pub fn get(name: impl AsRef<str>) -> Request {
-    Request::new(Method::Get, counter_path(name), HashMap::new(), None)
+pub fn get(name: impl AsRef<str>) -> JsonRequest<CounterVal> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        counter_path(name),
+        HashMap::new(),
+        None,
+    ))
+    .with_status_check(check_counter_status)
 }
 
-pub fn get_config(name: impl AsRef<str>) -> Request {
-    Request::new(Method::Get, counter_config_path(name), HashMap::new(), None)
+pub fn get_config(name: impl AsRef<str>) -> JsonRequest<Counter> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        counter_config_path(name),
+        HashMap::new(),
+        None,
+    ))
 }
 
+/// Increments the counter by 1, or by the delta set with
+/// [`IncrementCounterReq::by`]. Returns the new value of strong counters, and
+/// `None` for weak counters.
 pub fn increment(name: impl Into<String>) -> IncrementCounterReq {
     IncrementCounterReq::new(name)
 }
 
-pub fn decrement(name: impl AsRef<str>) -> Request {
-    Request::new(
+/// Returns the new value of strong counters, and `None` for weak counters,
+/// because the server does not return their value.
+pub fn decrement(name: impl AsRef<str>) -> JsonRequest<Option<CounterVal>> {
+    JsonRequest::new(Request::new(
         Method::Post,
         counter_path_with_action(name, &Action::Decrement),
         HashMap::new(),
         None,
-    )
+    ))
+    .with_status_check(check_counter_status)
 }
 
-pub fn reset(name: impl AsRef<str>) -> Request {
-    Request::new(
+pub fn reset(name: impl AsRef<str>) -> JsonRequest<()> {
+    JsonRequest::new(Request::new(
         Method::Post,
         counter_path_with_action(name, &Action::Reset),
         HashMap::new(),
         None,
-    )
+    ))
+    .with_status_check(check_counter_status)
 }
 
 pub fn delete(name: impl AsRef<str>) -> Request {
     Request::new(Method::Delete, counter_path(name), HashMap::new(), None)
 }
 
-pub fn compare_and_set(name: impl AsRef<str>, expect: CounterVal, update: CounterVal) -> Request {
-    Request::new(
+/// Sets the counter to `update` if its value is `expect`. Returns whether the
+/// value was updated.
+pub fn compare_and_set(
+    name: impl AsRef<str>,
+    expect: CounterVal,
+    update: CounterVal,
+) -> JsonRequest<bool> {
+    JsonRequest::new(Request::new(
         Method::Post,
         counter_path_with_action(name, &Action::CompareAndSet { expect, update }),
         HashMap::new(),
         None,
-    )
+    ))
+    .with_status_check(check_counter_status)
 }
 
-pub fn compare_and_swap(name: impl AsRef<str>, expect: CounterVal, update: CounterVal) -> Request {
-    Request::new(
+/// Sets the counter to `update` if its value is `expect`. Returns the value
+/// before the operation, so the counter was updated if it is `expect`.
+pub fn compare_and_swap(
+    name: impl AsRef<str>,
+    expect: CounterVal,
+    update: CounterVal,
+) -> JsonRequest<CounterVal> {
+    JsonRequest::new(Request::new(
         Method::Post,
         counter_path_with_action(name, &Action::CompareAndSwap { expect, update }),
         HashMap::new(),
         None,
-    )
+    ))
+    .with_status_check(check_counter_status)
 }
 
-pub fn list() -> Request {
-    Request::new(Method::Get, COUNTERS_ENDPOINT, HashMap::new(), None)
+/// Lists the names of the counters.
+pub fn list() -> JsonRequest<Vec<String>> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        COUNTERS_ENDPOINT,
+        HashMap::new(),
+        None,
+    ))
 }
 
 fn counter_path(name: impl AsRef<str>) -> String {
@@ -256,6 +532,68 @@ fn counter_path(name: impl AsRef<str>) -> String {
     )
 }
 
+fn create_counter_request(name: impl AsRef<str>, counter: &Counter) -> Request {
+    Request::new(
+        Method::Post,
+        counter_path(name),
+        HashMap::new(),
+        Some(json!(counter).to_string()),
+    )
+}
+
+/// Maps the error returned by the server when an update goes beyond the
+/// bounds of a strong counter. The server does not return a specific status,
+/// only the messages "Lower bound reached." and "Upper bound reached.", so
+/// the bounds are left empty here and filled by [`out_of_bounds_error`].
+fn check_counter_status(status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+    if !status.is_success() {
+        let message = body.to_lowercase();
+
+        if message.contains("lower bound reached") || message.contains("upper bound reached") {
+            return Err(InfinispanError::CounterOutOfBounds {
+                lower: None,
+                upper: None,
+            });
+        }
+    }
+
+    check_status(status, body)
+}
+
+/// Returns a [`InfinispanError::CounterOutOfBounds`] with the bounds of the
+/// counter updated by the request sent to `path`. The bounds are left empty
+/// if the configuration of the counter cannot be read.
+pub(crate) async fn out_of_bounds_error(client: &Infinispan, path: &str) -> InfinispanError {
+    let bounds = match counter_name_from_path(path) {
+        Some(name) => read_bounds(client, &name).await.ok(),
+        None => None,
+    };
+
+    let (lower, upper) = bounds.unwrap_or_default();
+    InfinispanError::CounterOutOfBounds { lower, upper }
+}
+
+async fn read_bounds(
+    client: &Infinispan,
+    name: &str,
+) -> Result<(Option<CounterVal>, Option<CounterVal>), InfinispanError> {
+    // Not sent with `run_typed`, which calls this on bound errors.
+    let request = get_config(name);
+    let resp = client.run(&request).await?;
+    let status = resp.status();
+    let body = resp.text_with_charset("utf-8").await?;
+
+    match request.parse_response(status, &body)? {
+        Counter::Strong(counter) => Ok((counter.lower_bound, counter.upper_bound)),
+        Counter::Weak(_) => Ok((None, None)),
+    }
+}
+
+fn counter_name_from_path(path: &str) -> Option<String> {
+    let name = path.split("/counters/").nth(1)?.split(['/', '?']).next()?;
+    urlencoding::decode(name).ok().map(Cow::into_owned)
+}
+
 fn counter_path_with_action(name: impl AsRef<str>, action: &Action) -> String {
     format!("{}?{}", counter_path(name), action.to_query_args())
 }
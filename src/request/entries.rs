@@ -65,6 +65,9 @@ pub fn create(cache_name: impl Into<String>, entry_name: impl Into<String>) -> C
     CreateEntryReq::new(cache_name, entry_name)
 }
 
+/// Always reads the entry from the server, even if the client has a near
+/// cache for the cache. Use [`Infinispan::get_entry`](crate::Infinispan::get_entry)
+/// to read from the near cache.
 pub fn get(cache_name: impl AsRef<str>, entry_name: impl AsRef<str>) -> Request {
     Request::new(
         Method::Get,
@@ -0,0 +1,374 @@
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_util::future;
+use futures_util::stream::{self, Stream, StreamExt};
+use http::header::ACCEPT;
+use serde_json::Value;
+
+use crate::errors::InfinispanError;
+use crate::request::caches::cache_url;
+use crate::request::{Method, Request, ToHttpRequest};
+use crate::Infinispan;
+
+const CONTENT_TYPE_EVENT_STREAM: &str = "text/event-stream";
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum CacheEventType {
+    Created,
+    Modified,
+    Removed,
+    Expired,
+}
+
+impl CacheEventType {
+    fn from_sse_event(event: &str) -> Option<Self> {
+        match event {
+            "cache-entry-created" => Some(CacheEventType::Created),
+            "cache-entry-modified" => Some(CacheEventType::Modified),
+            "cache-entry-removed" => Some(CacheEventType::Removed),
+            "cache-entry-expired" => Some(CacheEventType::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// A change of an entry, received from [`subscribe`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct CacheEvent {
+    pub event_type: CacheEventType,
+    pub key: String,
+    /// Only included by the server in some events.
+    pub value: Option<String>,
+}
+
+impl CacheEvent {
+    // Depending on the version, the server sends the key as the data of the
+    // event, or a JSON object with the key and the value.
+    fn from_sse(event: &str, data: &str) -> Option<Self> {
+        let event_type = CacheEventType::from_sse_event(event)?;
+
+        let as_string = |value: &Value| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+
+        let (key, value) = match serde_json::from_str::<Value>(data) {
+            Ok(Value::Object(event)) if event.contains_key("key") => {
+                (as_string(&event["key"]), event.get("value").map(as_string))
+            }
+            _ => (data.to_string(), None),
+        };
+
+        Some(Self {
+            event_type,
+            key,
+            value,
+        })
+    }
+}
+
+/// How long to wait before reconnecting after the event stream is closed or
+/// cannot be opened. The delay doubles with each consecutive failure, and
+/// goes back to the initial delay when a connection is opened.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Backoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_retries: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            max_retries: None,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Consecutive failures after which the stream returns the last error
+    /// and ends. By default, it retries forever.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    fn delay(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+
+        self.initial_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListenReq {
+    cache_name: String,
+    include_current_state: bool,
+    backoff: Backoff,
+}
+
+impl ListenReq {
+    pub fn new(cache_name: impl Into<String>) -> Self {
+        Self {
+            cache_name: cache_name.into(),
+            include_current_state: false,
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Receives a `Created` event for each entry in the cache before the
+    /// changes. This also happens on every reconnection, so the entries
+    /// changed while disconnected are not missed.
+    pub fn include_current_state(mut self) -> Self {
+        self.include_current_state = true;
+        self
+    }
+
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl From<&ListenReq> for Request {
+    fn from(request: &ListenReq) -> Self {
+        let mut path_and_query = format!("{}?action=listen", cache_url(&request.cache_name));
+
+        if request.include_current_state {
+            path_and_query.push_str("&includeCurrentState=true");
+        }
+
+        let mut headers = HashMap::new();
+        headers.insert(ACCEPT.to_string(), CONTENT_TYPE_EVENT_STREAM.to_string());
+
+        Self::new(Method::Get, path_and_query, headers, None)
+    }
+}
+
+impl ToHttpRequest for ListenReq {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+}
+
+pub fn listen(cache_name: impl Into<String>) -> ListenReq {
+    ListenReq::new(cache_name)
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<bytes::Bytes>> + Send>>;
+
+struct Subscription {
+    client: Infinispan,
+    request: ListenReq,
+    body: Option<ByteStream>,
+    parser: SseParser,
+    pending: VecDeque<SubscriptionEvent>,
+    failures: u32,
+    done: bool,
+}
+
+/// What happens in a subscription, including the connections that are not
+/// visible in [`subscribe`]. The near cache needs them to know when it can
+/// miss events.
+pub(crate) enum SubscriptionEvent {
+    Connected,
+    Disconnected,
+    Event(CacheEvent),
+}
+
+/// Returns the events of a cache as they happen. When the connection is
+/// closed, it reconnects following the [`Backoff`] of the request.
+///
+/// The stream ends with an error when the server rejects the request, for
+/// example, because the cache does not exist, or when the maximum number of
+/// retries is reached.
+pub fn subscribe(
+    client: &Infinispan,
+    request: ListenReq,
+) -> impl Stream<Item = Result<CacheEvent, InfinispanError>> + Send + 'static {
+    subscribe_with_connections(client, request).filter_map(|result| {
+        future::ready(match result {
+            Ok(SubscriptionEvent::Event(event)) => Some(Ok(event)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    })
+}
+
+pub(crate) fn subscribe_with_connections(
+    client: &Infinispan,
+    request: ListenReq,
+) -> impl Stream<Item = Result<SubscriptionEvent, InfinispanError>> + Send + 'static {
+    let subscription = Subscription {
+        client: client.clone(),
+        request,
+        body: None,
+        parser: SseParser::default(),
+        pending: VecDeque::new(),
+        failures: 0,
+        done: false,
+    };
+
+    stream::unfold(subscription, |mut subscription| async move {
+        loop {
+            if let Some(event) = subscription.pending.pop_front() {
+                return Some((Ok(event), subscription));
+            }
+
+            if subscription.done {
+                return None;
+            }
+
+            let body = match &mut subscription.body {
+                Some(body) => body,
+                None => {
+                    if let Err(err) = subscription.connect().await {
+                        return Some((Err(err), subscription));
+                    }
+
+                    continue;
+                }
+            };
+
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    let events = subscription.parser.push(&chunk);
+                    subscription.pending.extend(
+                        events
+                            .iter()
+                            .filter_map(|(event, data)| CacheEvent::from_sse(event, data))
+                            .map(SubscriptionEvent::Event),
+                    );
+                }
+                Some(Err(_)) | None => {
+                    subscription.body = None;
+                    subscription.failures += 1;
+                    subscription
+                        .pending
+                        .push_back(SubscriptionEvent::Disconnected);
+                }
+            }
+        }
+    })
+}
+
+impl Subscription {
+    // Only returns the errors that end the stream.
+    async fn connect(&mut self) -> Result<(), InfinispanError> {
+        if self.failures > 0 {
+            tokio::time::sleep(self.request.backoff.delay(self.failures)).await;
+        }
+
+        let err = match self.client.run(&self.request).await {
+            Ok(resp) if resp.status().is_success() => {
+                self.body = Some(Box::pin(resp.bytes_stream()));
+                self.parser = SseParser::default();
+                // A connection that is closed later, even without events,
+                // starts the backoff again from the initial delay.
+                self.failures = 0;
+                self.pending.push_back(SubscriptionEvent::Connected);
+                return Ok(());
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text_with_charset("utf-8").await.unwrap_or_default();
+                let err = InfinispanError::UnexpectedResponse { status, body };
+
+                // Errors like a missing cache will not be solved by retrying.
+                if status.is_client_error() {
+                    self.done = true;
+                    return Err(err);
+                }
+
+                err
+            }
+            Err(err) => err,
+        };
+
+        self.failures += 1;
+
+        if self
+            .request
+            .backoff
+            .max_retries
+            .is_some_and(|max_retries| self.failures > max_retries)
+        {
+            self.done = true;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
+
+// Parses the events of a "text/event-stream" body as it arrives. It returns
+// the name and the data of each complete event.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: String,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) -> Vec<(String, String)> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                let event = std::mem::take(&mut self.event);
+
+                if !self.data.is_empty() {
+                    events.push((event, self.data.join("\n")));
+                }
+
+                self.data.clear();
+                continue;
+            }
+
+            // Lines that start with ":" are comments, used as keep-alives.
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+
+            match field {
+                "event" => self.event = value.to_string(),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        events
+    }
+}
@@ -1,11 +1,19 @@
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
-use http::Request as HttpRequest;
+use http::{Request as HttpRequest, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::errors::InfinispanError;
 
 pub mod caches;
 pub mod counters;
 pub mod entries;
+pub mod events;
+pub mod query;
+pub mod schemas;
+pub mod templates;
 
 #[derive(Debug)]
 pub enum Method {
@@ -44,6 +52,25 @@ pub trait ToHttpRequest {
         base_url: impl AsRef<str>,
         basic_auth_encoded: impl AsRef<str>,
     ) -> HttpRequest<String>;
+
+    /// Checks the request before it is sent. Requests that carry
+    /// configuration override this to reject it early with
+    /// [`InfinispanError::InvalidConfig`].
+    fn validate(&self) -> Result<(), InfinispanError> {
+        Ok(())
+    }
+}
+
+/// Requests whose response can be parsed into a typed value. They can be sent
+/// with [`Infinispan::run_typed`](crate::Infinispan::run_typed).
+pub trait ParseResponse {
+    type Output;
+
+    fn parse_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) -> Result<Self::Output, InfinispanError>;
 }
 
 impl Request {
@@ -72,9 +99,18 @@ impl ToHttpRequest for Request {
             .method(self.method.as_str())
             .uri(format!("{}{}", base_url.as_ref(), self.path_and_query));
 
-        http_req = http_req
-            .header(CONTENT_TYPE, "application/json")
-            .header(AUTHORIZATION, basic_auth_encoded.as_ref());
+        // JSON is the default, but some requests send other formats, like the
+        // XML or YAML configuration of a cache.
+        let has_content_type = self
+            .headers
+            .keys()
+            .any(|header_name| header_name.eq_ignore_ascii_case(CONTENT_TYPE.as_str()));
+
+        if !has_content_type {
+            http_req = http_req.header(CONTENT_TYPE, "application/json");
+        }
+
+        http_req = http_req.header(AUTHORIZATION, basic_auth_encoded.as_ref());
 
         for (header_name, header_val) in &self.headers {
             http_req = http_req.header(header_name.as_str(), header_val);
@@ -89,3 +125,89 @@ impl ToHttpRequest for Request {
             .unwrap()
     }
 }
+
+impl ParseResponse for Request {
+    type Output = ();
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+        check_status(status, body)
+    }
+}
+
+/// A [`Request`] that returns JSON that can be deserialized into `T`.
+#[derive(Debug)]
+pub struct JsonRequest<T> {
+    pub request: Request,
+    check_status: StatusCheck,
+    output: PhantomData<fn() -> T>,
+}
+
+type StatusCheck = fn(StatusCode, &str) -> Result<(), InfinispanError>;
+
+impl<T> JsonRequest<T> {
+    pub fn new(request: Request) -> Self {
+        Self {
+            request,
+            check_status,
+            output: PhantomData,
+        }
+    }
+
+    /// Replaces the check of the response status, to map some errors of the
+    /// server to specific variants of [`InfinispanError`].
+    pub(crate) fn with_status_check(mut self, check_status: StatusCheck) -> Self {
+        self.check_status = check_status;
+        self
+    }
+}
+
+impl<T> ToHttpRequest for JsonRequest<T> {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> HttpRequest<String> {
+        self.request.to_http_req(base_url, basic_auth_encoded)
+    }
+}
+
+impl<T: DeserializeOwned> ParseResponse for JsonRequest<T> {
+    type Output = T;
+
+    fn parse_response(&self, status: StatusCode, body: &str) -> Result<T, InfinispanError> {
+        parse_json_with(self.check_status, status, body)
+    }
+}
+
+pub(crate) fn check_status(status: StatusCode, body: &str) -> Result<(), InfinispanError> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(InfinispanError::UnexpectedResponse {
+            status,
+            body: body.into(),
+        })
+    }
+}
+
+pub(crate) fn parse_json<T: DeserializeOwned>(
+    status: StatusCode,
+    body: &str,
+) -> Result<T, InfinispanError> {
+    parse_json_with(check_status, status, body)
+}
+
+// Empty bodies are parsed as null, so that they can be read into `()` or
+// `Option`.
+pub(crate) fn parse_json_with<T: DeserializeOwned>(
+    check_status: StatusCheck,
+    status: StatusCode,
+    body: &str,
+) -> Result<T, InfinispanError> {
+    check_status(status, body)?;
+
+    match body.trim() {
+        "" => Ok(serde_json::from_str("null")?),
+        body => Ok(serde_json::from_str(body)?),
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::errors::InfinispanError;
+
+/// Builds an Ickle query. Values are escaped when the query is rendered with
+/// [`Query::to_ickle`], so they can come from user input. Named parameters
+/// are also replaced by their escaped values in the rendered query, because
+/// the REST API does not accept parameters separately:
+///
+/// ```
+/// use infinispan::request::query::{field, param, Query};
+///
+/// let query = Query::from("sample.Person")
+///     .where_(field("age").gt(param("min_age")))
+///     .and(field("name").like("J%"))
+///     .order_by("age")
+///     .with_param("min_age", 30);
+///
+/// assert_eq!(
+///     "FROM sample.Person WHERE age > 30 AND name LIKE 'J%' ORDER BY age ASC",
+///     query.to_ickle().unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    entity: String,
+    projection: Vec<String>,
+    condition: Option<Condition>,
+    order_by: Vec<(String, SortOrder)>,
+    params: BTreeMap<String, Literal>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Query {
+    /// Queries the entries of a Protobuf message type, like "sample.Person".
+    pub fn from(entity: impl Into<String>) -> Self {
+        Self {
+            entity: entity.into(),
+            projection: Vec::new(),
+            condition: None,
+            order_by: Vec::new(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Returns only these fields instead of the whole entries.
+    pub fn select<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.projection = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the condition of the query. It replaces any previous one.
+    pub fn where_(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Adds a condition that must also be true.
+    pub fn and(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition {
+            Some(current) => current.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    /// Adds a condition that can be true instead of the current ones.
+    pub fn or(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition {
+            Some(current) => current.or(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn order_by(mut self, field: impl Into<String>) -> Self {
+        self.order_by.push((field.into(), SortOrder::Asc));
+        self
+    }
+
+    pub fn order_by_desc(mut self, field: impl Into<String>) -> Self {
+        self.order_by.push((field.into(), SortOrder::Desc));
+        self
+    }
+
+    /// Sets the value of a named parameter, referenced with [`param`]. The
+    /// value is written into the query when it is rendered, so it is not sent
+    /// as a server-side `:name` parameter.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<Literal>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Renders the query. It fails when a field name is not a valid
+    /// identifier, or when a parameter does not have a value.
+    pub fn to_ickle(&self) -> Result<String, InfinispanError> {
+        let mut ickle = String::new();
+
+        if !self.projection.is_empty() {
+            for field in &self.projection {
+                check_identifier(field)?;
+            }
+
+            write!(ickle, "SELECT {} ", self.projection.join(", ")).unwrap();
+        }
+
+        self.render_from_where(&mut ickle)?;
+
+        for (i, (field, order)) in self.order_by.iter().enumerate() {
+            check_identifier(field)?;
+
+            let order = match order {
+                SortOrder::Asc => "ASC",
+                SortOrder::Desc => "DESC",
+            };
+
+            let separator = if i == 0 { " ORDER BY " } else { ", " };
+            write!(ickle, "{}{} {}", separator, field, order).unwrap();
+        }
+
+        Ok(ickle)
+    }
+
+    /// Renders a `DELETE FROM` statement that removes the entries that match
+    /// the query. It fails if the query selects fields or is sorted.
+    pub fn to_delete_statement(&self) -> Result<String, InfinispanError> {
+        if !self.projection.is_empty() || !self.order_by.is_empty() {
+            return Err(InfinispanError::InvalidQuery(
+                "delete statements cannot select fields or be sorted".into(),
+            ));
+        }
+
+        let mut ickle = String::from("DELETE ");
+        self.render_from_where(&mut ickle)?;
+
+        Ok(ickle)
+    }
+
+    fn render_from_where(&self, ickle: &mut String) -> Result<(), InfinispanError> {
+        check_identifier(&self.entity)?;
+        write!(ickle, "FROM {}", self.entity).unwrap();
+
+        if let Some(condition) = &self.condition {
+            ickle.push_str(" WHERE ");
+            condition.render(&self.params, ickle)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A value in a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    /// A named parameter, whose value is set with [`Query::with_param`].
+    Param(String),
+}
+
+impl From<bool> for Literal {
+    fn from(value: bool) -> Self {
+        Literal::Bool(value)
+    }
+}
+
+impl From<i32> for Literal {
+    fn from(value: i32) -> Self {
+        Literal::Int(value.into())
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(value: i64) -> Self {
+        Literal::Int(value)
+    }
+}
+
+impl From<u32> for Literal {
+    fn from(value: u32) -> Self {
+        Literal::Int(value.into())
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::Float(value)
+    }
+}
+
+impl From<&str> for Literal {
+    fn from(value: &str) -> Self {
+        Literal::String(value.into())
+    }
+}
+
+impl From<String> for Literal {
+    fn from(value: String) -> Self {
+        Literal::String(value)
+    }
+}
+
+impl Literal {
+    fn render(
+        &self,
+        params: &BTreeMap<String, Literal>,
+        ickle: &mut String,
+    ) -> Result<(), InfinispanError> {
+        match self {
+            Literal::Bool(value) => write!(ickle, "{}", value).unwrap(),
+            Literal::Int(value) => write!(ickle, "{}", value).unwrap(),
+            Literal::Float(value) if value.is_finite() => write!(ickle, "{}", value).unwrap(),
+            Literal::Float(value) => {
+                return Err(InfinispanError::InvalidQuery(format!(
+                    "{} cannot be used in a query",
+                    value
+                )))
+            }
+            // Ickle accepts backslash escape sequences in string literals, so
+            // backslashes are escaped too. Otherwise, a value ending with one
+            // would escape the closing quote.
+            Literal::String(value) => write!(
+                ickle,
+                "'{}'",
+                value.replace('\\', "\\\\").replace('\'', "''")
+            )
+            .unwrap(),
+            Literal::Param(name) => match params.get(name) {
+                Some(Literal::Param(_)) | None => {
+                    return Err(InfinispanError::InvalidQuery(format!(
+                        "parameter {} does not have a value",
+                        name
+                    )))
+                }
+                Some(value) => value.render(params, ickle)?,
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// References a named parameter in a condition.
+pub fn param(name: impl Into<String>) -> Literal {
+    Literal::Param(name.into())
+}
+
+/// A field of the queried entity, like "age" or "address.city".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    name: String,
+}
+
+pub fn field(name: impl Into<String>) -> Field {
+    Field { name: name.into() }
+}
+
+impl Field {
+    pub fn eq(self, value: impl Into<Literal>) -> Condition {
+        self.compare("=", value)
+    }
+
+    pub fn ne(self, value: impl Into<Literal>) -> Condition {
+        self.compare("!=", value)
+    }
+
+    pub fn gt(self, value: impl Into<Literal>) -> Condition {
+        self.compare(">", value)
+    }
+
+    pub fn gte(self, value: impl Into<Literal>) -> Condition {
+        self.compare(">=", value)
+    }
+
+    pub fn lt(self, value: impl Into<Literal>) -> Condition {
+        self.compare("<", value)
+    }
+
+    pub fn lte(self, value: impl Into<Literal>) -> Condition {
+        self.compare("<=", value)
+    }
+
+    /// Matches a pattern where "%" is any sequence of characters and "_" is
+    /// any character.
+    pub fn like(self, pattern: impl Into<Literal>) -> Condition {
+        self.compare("LIKE", pattern)
+    }
+
+    pub fn in_<I, L>(self, values: I) -> Condition
+    where
+        I: IntoIterator<Item = L>,
+        L: Into<Literal>,
+    {
+        Condition(Expr::In {
+            field: self.name,
+            values: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    pub fn is_null(self) -> Condition {
+        Condition(Expr::IsNull {
+            field: self.name,
+            negated: false,
+        })
+    }
+
+    pub fn is_not_null(self) -> Condition {
+        Condition(Expr::IsNull {
+            field: self.name,
+            negated: true,
+        })
+    }
+
+    fn compare(self, operator: &'static str, value: impl Into<Literal>) -> Condition {
+        Condition(Expr::Compare {
+            field: self.name,
+            operator,
+            value: value.into(),
+        })
+    }
+}
+
+/// A condition of a [`Query`], built from a [`field`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition(Expr);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare {
+        field: String,
+        operator: &'static str,
+        value: Literal,
+    },
+    In {
+        field: String,
+        values: Vec<Literal>,
+    },
+    IsNull {
+        field: String,
+        negated: bool,
+    },
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn and(self, other: Condition) -> Condition {
+        Condition(Expr::And(Box::new(self), Box::new(other)))
+    }
+
+    pub fn or(self, other: Condition) -> Condition {
+        Condition(Expr::Or(Box::new(self), Box::new(other)))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Condition {
+        Condition(Expr::Not(Box::new(self)))
+    }
+
+    fn render(
+        &self,
+        params: &BTreeMap<String, Literal>,
+        ickle: &mut String,
+    ) -> Result<(), InfinispanError> {
+        match &self.0 {
+            Expr::Compare {
+                field,
+                operator,
+                value,
+            } => {
+                check_identifier(field)?;
+                write!(ickle, "{} {} ", field, operator).unwrap();
+                value.render(params, ickle)?;
+            }
+            Expr::In { field, values } => {
+                check_identifier(field)?;
+                write!(ickle, "{} IN (", field).unwrap();
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        ickle.push_str(", ");
+                    }
+
+                    value.render(params, ickle)?;
+                }
+
+                ickle.push(')');
+            }
+            Expr::IsNull { field, negated } => {
+                check_identifier(field)?;
+                let not = if *negated { "NOT " } else { "" };
+                write!(ickle, "{} IS {}NULL", field, not).unwrap();
+            }
+            Expr::And(left, right) => {
+                left.render_operand(params, ickle)?;
+                ickle.push_str(" AND ");
+                right.render_operand(params, ickle)?;
+            }
+            Expr::Or(left, right) => {
+                left.render_operand(params, ickle)?;
+                ickle.push_str(" OR ");
+                right.render_operand(params, ickle)?;
+            }
+            Expr::Not(condition) => {
+                ickle.push_str("NOT ");
+                condition.render_operand(params, ickle)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Conditions that combine others are wrapped in parentheses, so that the
+    // precedence of AND and OR does not change their meaning.
+    fn render_operand(
+        &self,
+        params: &BTreeMap<String, Literal>,
+        ickle: &mut String,
+    ) -> Result<(), InfinispanError> {
+        match &self.0 {
+            Expr::And(..) | Expr::Or(..) => {
+                ickle.push('(');
+                self.render(params, ickle)?;
+                ickle.push(')');
+            }
+            _ => self.render(params, ickle)?,
+        }
+
+        Ok(())
+    }
+}
+
+// Field and entity names are written as they are, so they are restricted to
+// dotted identifiers.
+fn check_identifier(name: &str) -> Result<(), InfinispanError> {
+    let is_identifier = |segment: &str| {
+        let mut chars = segment.chars();
+
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+
+    if name.split('.').all(is_identifier) {
+        Ok(())
+    } else {
+        Err(InfinispanError::InvalidQuery(format!(
+            "{:?} is not a valid field or entity name",
+            name
+        )))
+    }
+}
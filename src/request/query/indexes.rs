@@ -0,0 +1,136 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::request::caches::cache_url;
+use crate::request::{JsonRequest, Method, Request};
+
+/// Statistics of the indexes of a cache, returned by [`index_stats`].
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct IndexStats {
+    /// Statistics by indexed entity, like "sample.Person".
+    #[serde(default)]
+    pub types: BTreeMap<String, IndexTypeStats>,
+    /// Whether the cache is being reindexed.
+    #[serde(default)]
+    pub reindexing: bool,
+    /// Attributes that are not modeled, like the ones of older servers.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct IndexTypeStats {
+    /// Number of indexed entities.
+    pub count: i64,
+    /// Size of the index in bytes.
+    pub size: i64,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Statistics of the queries run in a cache, returned by [`query_stats`].
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_local: Option<QueryTypeStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_distributed: Option<QueryTypeStats>,
+    /// Queries that use the indexes only for some of their conditions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<QueryTypeStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_indexed: Option<QueryTypeStats>,
+    /// Loading of the entries that matched indexed queries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_load: Option<QueryTypeStats>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct QueryTypeStats {
+    pub count: i64,
+    /// Average time in nanoseconds.
+    pub average: f64,
+    /// Maximum time in nanoseconds.
+    pub max: i64,
+    /// The query that took `max` to run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slowest: Option<String>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Runs an Ickle `DELETE FROM` statement, like
+/// "DELETE FROM sample.Person WHERE age < 18". It can be rendered with
+/// [`Query::to_delete_statement`](super::Query::to_delete_statement).
+pub fn delete_by_query(cache_name: impl AsRef<str>, statement: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        format!(
+            "{}?action=deleteByQuery&query={}",
+            cache_url(cache_name),
+            urlencoding::encode(statement.as_ref())
+        ),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// Rebuilds the indexes of a cache from its entries. The server returns
+/// before the reindexing finishes, which can be checked in [`index_stats`].
+pub fn reindex(cache_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        format!("{}?action=reindex", indexes_url(cache_name)),
+        HashMap::new(),
+        None,
+    )
+}
+
+/// Removes all the data from the indexes of a cache. The entries are kept.
+pub fn clear_indexes(cache_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        format!("{}?action=clear", indexes_url(cache_name)),
+        HashMap::new(),
+        None,
+    )
+}
+
+pub fn index_stats(cache_name: impl AsRef<str>) -> JsonRequest<IndexStats> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        format!("{}/stats", indexes_url(cache_name)),
+        HashMap::new(),
+        None,
+    ))
+}
+
+pub fn query_stats(cache_name: impl AsRef<str>) -> JsonRequest<QueryStats> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        query_stats_url(cache_name),
+        HashMap::new(),
+        None,
+    ))
+}
+
+pub fn reset_query_stats(cache_name: impl AsRef<str>) -> Request {
+    Request::new(
+        Method::Post,
+        format!("{}?action=clear", query_stats_url(cache_name)),
+        HashMap::new(),
+        None,
+    )
+}
+
+fn indexes_url(cache_name: impl AsRef<str>) -> String {
+    format!("{}/search/indexes", cache_url(cache_name))
+}
+
+fn query_stats_url(cache_name: impl AsRef<str>) -> String {
+    format!("{}/search/query/stats", cache_url(cache_name))
+}
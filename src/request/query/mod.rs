@@ -0,0 +1,245 @@
+mod builder;
+mod indexes;
+
+pub use builder::*;
+pub use indexes::*;
+
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+
+use futures_util::stream::{self, Stream};
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::errors::InfinispanError;
+use crate::request::caches::cache_url;
+use crate::request::{parse_json, Method, ParseResponse, Request, ToHttpRequest};
+use crate::Infinispan;
+
+/// Runs an Ickle query, like "FROM example.Person WHERE age > 30", and
+/// deserializes the hits into `T`.
+///
+/// The entries need to be stored with Protobuf encoding, and `T` is
+/// deserialized from their JSON representation, that includes a `_type`
+/// field with the name of the Protobuf message.
+#[derive(Debug)]
+pub struct SearchReq<T> {
+    cache_name: String,
+    query: String,
+    offset: Option<u32>,
+    max_results: Option<u32>,
+    hit_count_accuracy: Option<u32>,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<T> SearchReq<T> {
+    pub fn new(cache_name: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            cache_name: cache_name.into(),
+            query: query.into(),
+            offset: None,
+            max_results: None,
+            hit_count_accuracy: None,
+            output: PhantomData,
+        }
+    }
+
+    /// Number of hits to skip.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Maximum number of hits to return. The server returns 10 by default.
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Up to which number of hits the hit count must be exact. Higher values
+    /// make indexed queries slower.
+    pub fn with_hit_count_accuracy(mut self, hit_count_accuracy: u32) -> Self {
+        self.hit_count_accuracy = Some(hit_count_accuracy);
+        self
+    }
+}
+
+impl<T> From<&SearchReq<T>> for Request {
+    fn from(request: &SearchReq<T>) -> Self {
+        let mut path_and_query = format!(
+            "{}?action=search&query={}",
+            cache_url(&request.cache_name),
+            urlencoding::encode(&request.query)
+        );
+
+        for (name, value) in [
+            ("offset", request.offset),
+            ("max_results", request.max_results),
+            ("hit_count_accuracy", request.hit_count_accuracy),
+        ] {
+            if let Some(value) = value {
+                path_and_query.push_str(&format!("&{}={}", name, value));
+            }
+        }
+
+        Self::new(Method::Get, path_and_query, HashMap::new(), None)
+    }
+}
+
+impl<T> ToHttpRequest for SearchReq<T> {
+    fn to_http_req(
+        &self,
+        base_url: impl AsRef<str>,
+        basic_auth_encoded: impl AsRef<str>,
+    ) -> http::Request<String> {
+        Request::from(self).to_http_req(base_url, basic_auth_encoded)
+    }
+}
+
+impl<T: DeserializeOwned> ParseResponse for SearchReq<T> {
+    type Output = SearchResults<T>;
+
+    fn parse_response(
+        &self,
+        status: StatusCode,
+        body: &str,
+    ) -> Result<SearchResults<T>, InfinispanError> {
+        let results: RawSearchResults<T> = parse_json(status, body)?;
+
+        Ok(SearchResults {
+            hit_count: results.hit_count,
+            hit_count_exact: results.hit_count_exact,
+            hits: results.hits.into_iter().map(|hit| hit.hit).collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResults<T> {
+    /// Total number of hits of the query, not only the ones returned.
+    pub hit_count: u64,
+    /// Whether `hit_count` is exact or a lower bound. See
+    /// [`SearchReq::with_hit_count_accuracy`].
+    pub hit_count_exact: bool,
+    pub hits: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct RawSearchResults<T> {
+    hit_count: u64,
+    #[serde(default = "exact_by_default")]
+    hit_count_exact: bool,
+    hits: Vec<RawHit<T>>,
+}
+
+#[derive(Deserialize)]
+struct RawHit<T> {
+    hit: T,
+}
+
+// Older servers do not return `hit_count_exact`, and their count is exact.
+fn exact_by_default() -> bool {
+    true
+}
+
+pub fn search<T>(cache_name: impl Into<String>, query: impl Into<String>) -> SearchReq<T> {
+    SearchReq::new(cache_name, query)
+}
+
+/// Runs a query page by page. Each page is a request with the same query and
+/// a different offset.
+///
+/// The offset of the request is where the first page starts, and its max
+/// results, when set, limits the total number of hits.
+#[derive(Debug)]
+pub struct Paginator<'a, T> {
+    client: &'a Infinispan,
+    request: SearchReq<T>,
+    page_size: u32,
+    offset: u32,
+    remaining: Option<u32>,
+    done: bool,
+}
+
+impl<'a, T: DeserializeOwned> Paginator<'a, T> {
+    pub fn new(client: &'a Infinispan, request: SearchReq<T>, page_size: u32) -> Self {
+        Self {
+            client,
+            offset: request.offset.unwrap_or_default(),
+            remaining: request.max_results,
+            request,
+            page_size: page_size.max(1),
+            done: false,
+        }
+    }
+
+    /// Returns the hits of the next page, or `None` when there are no more.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<T>>, InfinispanError> {
+        if self.done || self.remaining == Some(0) {
+            return Ok(None);
+        }
+
+        let page_size = match self.remaining {
+            Some(remaining) => remaining.min(self.page_size),
+            None => self.page_size,
+        };
+
+        self.request.offset = Some(self.offset);
+        self.request.max_results = Some(page_size);
+
+        let results = self.client.run_typed(&self.request).await?;
+        let returned = results.hits.len() as u32;
+
+        self.offset += returned;
+        self.remaining = self
+            .remaining
+            .map(|remaining| remaining.saturating_sub(returned));
+        // The server can return fewer hits than requested when it caps the
+        // max results, so only an exact hit count or an empty page mean that
+        // there are no more hits.
+        self.done = returned == 0
+            || (results.hit_count_exact && u64::from(self.offset) >= results.hit_count);
+
+        if returned == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(results.hits))
+        }
+    }
+
+    /// Returns the hits one by one, requesting the pages as they are needed.
+    /// The stream ends after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T, InfinispanError>> + 'a
+    where
+        T: 'a,
+    {
+        stream::unfold(
+            (self, VecDeque::new()),
+            |(mut paginator, mut buffer)| async move {
+                loop {
+                    if let Some(hit) = buffer.pop_front() {
+                        return Some((Ok(hit), (paginator, buffer)));
+                    }
+
+                    match paginator.next_page().await {
+                        Ok(Some(hits)) => buffer.extend(hits),
+                        Ok(None) => return None,
+                        Err(err) => {
+                            paginator.done = true;
+                            return Some((Err(err), (paginator, buffer)));
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
+pub fn paginate<T: DeserializeOwned>(
+    client: &Infinispan,
+    request: SearchReq<T>,
+    page_size: u32,
+) -> Paginator<'_, T> {
+    Paginator::new(client, request, page_size)
+}
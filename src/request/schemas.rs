@@ -0,0 +1,55 @@
+use crate::request::{Method, Request};
+use http::header::CONTENT_TYPE;
+use std::collections::HashMap;
+
+const SCHEMAS_ENDPOINT: &str = "/rest/v2/schemas";
+const CONTENT_TYPE_TEXT_PLAIN: &str = "text/plain";
+
+/// Registers a Protobuf schema. `name` usually has the ".proto" extension.
+pub fn create(name: impl AsRef<str>, content: impl Into<String>) -> Request {
+    Request::new(
+        Method::Post,
+        schema_url(name),
+        text_plain_headers(),
+        Some(content.into()),
+    )
+}
+
+/// Returns the content of the schema as plain text.
+pub fn get(name: impl AsRef<str>) -> Request {
+    Request::new(Method::Get, schema_url(name), HashMap::new(), None)
+}
+
+pub fn update(name: impl AsRef<str>, content: impl Into<String>) -> Request {
+    Request::new(
+        Method::Put,
+        schema_url(name),
+        text_plain_headers(),
+        Some(content.into()),
+    )
+}
+
+pub fn delete(name: impl AsRef<str>) -> Request {
+    Request::new(Method::Delete, schema_url(name), HashMap::new(), None)
+}
+
+pub fn list() -> Request {
+    Request::new(Method::Get, SCHEMAS_ENDPOINT, HashMap::new(), None)
+}
+
+fn schema_url(name: impl AsRef<str>) -> String {
+    format!(
+        "{schemas_endpoint}/{schema_name}",
+        schemas_endpoint = SCHEMAS_ENDPOINT,
+        schema_name = urlencoding::encode(name.as_ref())
+    )
+}
+
+fn text_plain_headers() -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    headers.insert(
+        CONTENT_TYPE.to_string(),
+        CONTENT_TYPE_TEXT_PLAIN.to_string(),
+    );
+    headers
+}
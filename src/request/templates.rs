@@ -0,0 +1,36 @@
+use crate::request::caches::Cache;
+use crate::request::{JsonRequest, Method, Request};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const CACHE_CONFIGS_ENDPOINT: &str = "/rest/v2/cache-configs";
+
+/// A configuration template available in the server, either predefined (like
+/// "org.infinispan.DIST_SYNC") or defined by the user.
+#[derive(Eq, PartialEq, Debug, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub configuration: Cache,
+}
+
+pub fn list() -> JsonRequest<Vec<Template>> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        format!("{}/templates", CACHE_CONFIGS_ENDPOINT),
+        HashMap::new(),
+        None,
+    ))
+}
+
+pub fn get_config(name: impl AsRef<str>) -> JsonRequest<Cache> {
+    JsonRequest::new(Request::new(
+        Method::Get,
+        format!(
+            "{cache_configs_endpoint}/{template_name}",
+            cache_configs_endpoint = CACHE_CONFIGS_ENDPOINT,
+            template_name = urlencoding::encode(name.as_ref())
+        ),
+        HashMap::new(),
+        None,
+    ))
+}
@@ -0,0 +1,45 @@
+// Cross-site operations need two Infinispan clusters, so these tests only
+// check the requests that are built.
+
+#[cfg(test)]
+mod backups {
+    use infinispan::request::caches::backups;
+    use infinispan::request::Method;
+    use serde_json::Value;
+
+    #[test]
+    fn take_offline() {
+        let req = backups::take_offline("some cache", "NYC");
+
+        assert!(matches!(req.method, Method::Post));
+        assert_eq!(
+            "/rest/v2/caches/some%20cache/x-site/backups/NYC?action=take-offline",
+            req.path_and_query
+        );
+    }
+
+    #[test]
+    fn push_state_status() {
+        let req = backups::push_state_status("some_cache");
+
+        assert!(matches!(req.method, Method::Get));
+        assert_eq!(
+            "/rest/v2/caches/some_cache/x-site/backups?action=push-state-status",
+            req.path_and_query
+        );
+    }
+
+    #[test]
+    fn set_take_offline_config() {
+        let req = backups::set_take_offline_config("some_cache", "LON", 3, 60_000);
+        let body: Value = serde_json::from_str(req.body.as_ref().unwrap()).unwrap();
+
+        assert!(matches!(req.method, Method::Put));
+        assert_eq!(
+            "/rest/v2/caches/some_cache/x-site/backups/LON/take-offline-config",
+            req.path_and_query
+        );
+        assert_eq!(3, body["after_failures"]);
+        assert_eq!(60_000, body["min_wait"]);
+    }
+}
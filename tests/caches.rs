@@ -5,7 +5,7 @@ mod caches {
     use crate::helpers::*;
     use http::StatusCode;
     use infinispan::request::caches::modes::*;
-    use infinispan::request::caches::Cache;
+    use infinispan::request::caches::{Availability, Cache, CacheHealth, CacheMode, CacheStatus};
     use infinispan::request::{caches, entries};
     use reqwest::Response;
     use serde_json::Value;
@@ -118,6 +118,271 @@ mod caches {
         );
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn create_with_memory() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache = Cache::Local(
+            Local::default().with_memory(
+                Memory::default()
+                    .with_max_count(1_000)
+                    .with_when_full(EvictionStrategy::Remove),
+            ),
+        );
+
+        let _ = run(&caches::create(cache_name, cache)).await;
+
+        assert_eq!(
+            get_cache_config(cache_name).await,
+            Cache::Local(
+                Local::default().with_memory(
+                    Memory::default()
+                        .with_max_count(1_000)
+                        .with_when_full(EvictionStrategy::Remove),
+                ),
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_with_encoding() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache =
+            Cache::Distributed(Distributed::create_sync().with_encoding(Encoding::protostream()));
+
+        let _ = run(&caches::create(cache_name, cache)).await;
+
+        assert_eq!(
+            get_cache_config(cache_name).await,
+            Cache::Distributed(Distributed::create_sync().with_encoding(Encoding::protostream()))
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_with_persistence() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let persistence = || {
+            Persistence::default()
+                .with_passivation(false)
+                .with_file_store(FileStore::default().with_preload(true))
+        };
+
+        let _ = run(&caches::create(
+            cache_name,
+            Cache::Local(Local::default().with_persistence(persistence())),
+        ))
+        .await;
+
+        assert_eq!(
+            get_cache_config(cache_name).await,
+            Cache::Local(Local::default().with_persistence(persistence()))
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_transactional() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache = || {
+            Cache::Local(
+                Local::default()
+                    .with_isolation(IsolationLevel::RepeatableRead)
+                    .with_transaction(
+                        Transaction::new(TransactionMode::NonXa)
+                            .with_locking(LockingMode::Pessimistic),
+                    ),
+            )
+        };
+
+        let _ = run(&caches::create(cache_name, cache())).await;
+
+        assert_eq!(get_cache_config(cache_name).await, cache());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_with_partition_handling() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache = || {
+            Cache::Distributed(
+                Distributed::create_sync().with_partition_handling(
+                    PartitionHandling::default()
+                        .with_when_split(WhenSplit::AllowReads)
+                        .with_merge_policy(MergePolicy::RemoveAll),
+                ),
+            )
+        };
+
+        let _ = run(&caches::create(cache_name, cache())).await;
+
+        assert_eq!(get_cache_config(cache_name).await, cache());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn availability() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+
+        let _ = run(&caches::create_distributed_sync(cache_name)).await;
+
+        let availability = run_typed(&caches::get_availability(cache_name)).await;
+        assert_eq!(Availability::Available, availability);
+
+        let resp = run(&caches::set_availability(
+            cache_name,
+            Availability::Available,
+        ))
+        .await;
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn rebalancing() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+
+        let _ = run(&caches::create_distributed_sync(cache_name)).await;
+
+        let resp = run(&caches::disable_rebalancing(cache_name)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&caches::enable_rebalancing(cache_name)).await;
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn distribution() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+
+        let _ = run(&caches::create_distributed_sync(cache_name)).await;
+        let _ = run(&entries::create(cache_name, "some_entry")).await;
+
+        let nodes = run_typed(&caches::distribution(cache_name)).await;
+        assert_eq!(1, nodes.len());
+        assert_eq!(1, nodes[0].total_entries);
+
+        let key_distribution = run_typed(&caches::key_distribution(cache_name, "some_entry")).await;
+        assert!(key_distribution.contains_key);
+        assert_eq!(nodes[0].node_name, key_distribution.owners[0].node_name);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_from_xml() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let xml = r#"<local-cache><memory max-count="1000"/></local-cache>"#;
+
+        let resp = run(&caches::create_from_xml(cache_name, xml)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&caches::get_config(cache_name)).await;
+        let config: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert!(!config["local-cache"]["memory"].is_null());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_from_yaml() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let yaml = "localCache:\n  memory:\n    maxCount: 1000\n";
+
+        let resp = run(&caches::create_from_yaml(cache_name, yaml)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&caches::get_config(cache_name)).await;
+        let config: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert!(!config["local-cache"]["memory"].is_null());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_from_template() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+
+        let resp = run(&caches::create_from_template(
+            cache_name,
+            "org.infinispan.REPL_SYNC",
+        ))
+        .await;
+        assert!(resp.status().is_success());
+
+        assert!(matches!(
+            get_cache_config(cache_name).await,
+            Cache::Replicated(_)
+        ));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn update_config() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache = |max_count| {
+            Cache::Distributed(
+                Distributed::create_sync().with_memory(Memory::default().with_max_count(max_count)),
+            )
+        };
+
+        let _ = run(&caches::create(cache_name, cache(1_000))).await;
+
+        let resp = run(&caches::update_config(cache_name, cache(2_000))).await;
+        assert!(resp.status().is_success());
+
+        assert_eq!(get_cache_config(cache_name).await, cache(2_000));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn mutable_attributes() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+        let cache = |max_count| {
+            Cache::Local(Local::default().with_memory(Memory::default().with_max_count(max_count)))
+        };
+
+        let _ = run(&caches::create(cache_name, cache(1_000))).await;
+
+        let mutable_attributes = run_typed(&caches::get_mutable_attributes(cache_name)).await;
+        assert!(mutable_attributes.contains(&"memory.max-count".to_string()));
+
+        let resp = run(&caches::set_mutable_attribute(
+            cache_name,
+            "memory.max-count",
+            2_000,
+        ))
+        .await;
+        assert!(resp.status().is_success());
+
+        assert_eq!(get_cache_config(cache_name).await, cache(2_000));
+    }
+
     #[tokio::test]
     #[serial]
     async fn get() {
@@ -127,12 +392,12 @@ mod caches {
 
         let _ = run(&caches::create_local(cache_name)).await;
 
-        let resp = run(&caches::get(cache_name)).await;
-        let info: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        let info = run_typed(&caches::get(cache_name)).await;
 
-        // Basic checks
-        assert!(!info["stats"].is_null());
-        assert!(!info["configuration"].is_null());
+        assert_eq!(CacheMode::Local, info.mode);
+        assert!(matches!(info.configuration, Cache::Local(_)));
+        assert_eq!(0, info.size);
+        assert!(!info.transactional);
     }
 
     #[tokio::test]
@@ -227,12 +492,35 @@ mod caches {
 
         let _ = run(&caches::create_local(cache_name)).await;
 
-        let resp = run(&caches::stats(cache_name)).await;
-        let config: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        let _ = run(&entries::create(cache_name, "some_entry")).await;
+        let _ = run(&entries::get(cache_name, "some_entry")).await;
+        let _ = run(&entries::get(cache_name, "missing_entry")).await;
 
-        // Basic check
-        assert!(!config["time_since_start"].is_null());
-        assert!(!config["time_since_reset"].is_null());
+        let stats = run_typed(&caches::stats(cache_name)).await;
+
+        assert!(stats.time_since_start >= 0);
+        assert_eq!(1, stats.stores);
+        assert_eq!(1, stats.hits);
+        assert_eq!(1, stats.misses);
+        assert_eq!(0.5, stats.hit_ratio);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reset_stats() {
+        cleanup().await;
+
+        let cache_name = "test_cache";
+
+        let _ = run(&caches::create_local(cache_name)).await;
+
+        let _ = run(&entries::create(cache_name, "some_entry")).await;
+
+        let resp = run(&caches::reset_stats(cache_name)).await;
+        assert!(resp.status().is_success());
+
+        let stats = run_typed(&caches::stats(cache_name)).await;
+        assert_eq!(0, stats.stores);
     }
 
     #[tokio::test]
@@ -252,6 +540,32 @@ mod caches {
         assert_eq!(cache_names, cache_names_from_list_resp(resp).await);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn list_detailed() {
+        cleanup().await;
+
+        let _ = run(&caches::create_local("cache_1")).await;
+        let _ = run(&caches::create_distributed_sync("cache_2")).await;
+
+        let mut caches = run_typed(&caches::list_detailed()).await;
+        caches.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            vec!["cache_1", "cache_2"],
+            caches
+                .iter()
+                .map(|cache| cache.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("local-cache", caches[0].cache_type);
+        assert_eq!("distributed-cache", caches[1].cache_type);
+        assert!(caches
+            .iter()
+            .all(|cache| cache.status == CacheStatus::Running
+                && cache.health == CacheHealth::Healthy));
+    }
+
     async fn cleanup() {
         let resp = run(&caches::list()).await;
 
@@ -265,7 +579,6 @@ mod caches {
     }
 
     async fn get_cache_config(name: impl AsRef<str>) -> Cache {
-        let resp = run(&caches::get_config(name)).await;
-        serde_json::from_str::<Cache>(&read_body(resp).await).unwrap()
+        run_typed(&caches::get_config(name)).await
     }
 }
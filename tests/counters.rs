@@ -7,8 +7,8 @@ mod helpers;
 mod counters {
     use crate::helpers::*;
     use http::StatusCode;
-    use infinispan::request::counters;
-    use reqwest::Response;
+    use infinispan::errors::InfinispanError;
+    use infinispan::request::counters::{self, Counter, CounterStorage, CounterType};
     use serde_json::Value;
     use serial_test::serial;
     use std::collections::HashSet;
@@ -81,14 +81,37 @@ mod counters {
         let initial_val = 10;
 
         let _ = run(&counters::create_strong(counter_name).with_value(initial_val)).await;
-        let resp = run(&counters::get_config(counter_name)).await;
+        let config = run_typed(&counters::get_config(counter_name)).await;
 
-        assert!(resp.status().is_success());
+        assert_eq!(CounterType::Strong, config.counter_type());
+        assert_eq!(Some(counter_name), config.name());
+        assert_eq!(Some(initial_val), config.initial_value());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_counters_with_config() {
+        cleanup().await;
 
+        let _ = run(&counters::create_weak("weak_counter")
+            .with_storage(CounterStorage::Persistent)
+            .with_concurrency_level(32))
+        .await;
+        let _ = run(&counters::create_strong("strong_counter")
+            .with_value(5)
+            .with_lower_bound(0)
+            .with_upper_bound(10))
+        .await;
+
+        let resp = run(&counters::get_config("weak_counter")).await;
         let config: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert_eq!("PERSISTENT", config["weak-counter"]["storage"]);
+        assert_eq!(32, config["weak-counter"]["concurrency-level"]);
 
-        assert_eq!(counter_name, config["strong-counter"]["name"]);
-        assert_eq!(initial_val, config["strong-counter"]["initial-value"]);
+        let resp = run(&counters::get_config("strong_counter")).await;
+        let config: Value = serde_json::from_str(&read_body(resp).await).unwrap();
+        assert_eq!(0, config["strong-counter"]["lower-bound"]);
+        assert_eq!(10, config["strong-counter"]["upper-bound"]);
     }
 
     #[tokio::test]
@@ -210,6 +233,77 @@ mod counters {
         assert_eq!("2", read_body(resp).await);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn typed_results() {
+        cleanup().await;
+
+        let counter_name = "test_counter";
+
+        let _ = run(&counters::create_strong(counter_name).with_value(1)).await;
+
+        assert_eq!(
+            Some(3),
+            run_typed(&counters::increment(counter_name).by(2)).await
+        );
+        assert_eq!(Some(2), run_typed(&counters::decrement(counter_name)).await);
+        assert!(!run_typed(&counters::compare_and_set(counter_name, 0, 5)).await);
+        assert!(run_typed(&counters::compare_and_set(counter_name, 2, 5)).await);
+        assert_eq!(
+            5,
+            run_typed(&counters::compare_and_swap(counter_name, 5, 6)).await
+        );
+        assert_eq!(6, run_typed(&counters::get(counter_name)).await);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn out_of_bounds() {
+        cleanup().await;
+
+        let counter_name = "test_counter";
+
+        let _ = run(&counters::create_strong(counter_name)
+            .with_lower_bound(0)
+            .with_upper_bound(1))
+        .await;
+
+        let result = infinispan_client()
+            .run_typed(&counters::increment(counter_name).by(2))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(InfinispanError::CounterOutOfBounds {
+                lower: Some(0),
+                upper: Some(1)
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_bounds_on_out_of_bounds_errors() {
+        let client = fake_server_with_responses(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 19\r\nConnection: close\r\n\r\n\
+             Upper bound reached",
+            "HTTP/1.1 200 OK\r\nContent-Length: 54\r\nConnection: close\r\n\r\n\
+             {\"strong-counter\":{\"lower-bound\":-5,\"upper-bound\":10}}",
+        ])
+        .await;
+
+        let result = client
+            .run_typed(&counters::increment("some counter").by(20))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(InfinispanError::CounterOutOfBounds {
+                lower: Some(-5),
+                upper: Some(10)
+            })
+        ));
+    }
+
     #[tokio::test]
     #[serial]
     async fn list() {
@@ -222,20 +316,37 @@ mod counters {
             let _ = run(&counters::create_weak(counter_name)).await;
         }
 
-        let resp = run(&counters::list()).await;
+        let listed: HashSet<String> = run_typed(&counters::list()).await.into_iter().collect();
 
-        assert_eq!(counter_names, counter_names_from_list_resp(resp).await);
+        assert_eq!(counter_names, listed);
     }
 
-    async fn cleanup() {
-        let resp = run(&counters::list()).await;
+    #[tokio::test]
+    #[serial]
+    async fn counter_configs() {
+        cleanup().await;
 
-        for counter_name in counter_names_from_list_resp(resp).await {
-            let _ = run(&counters::delete(counter_name)).await;
+        let _ = run(&counters::create_weak("weak_counter").with_concurrency_level(32)).await;
+        let _ = run(&counters::create_strong("strong_counter").with_upper_bound(10)).await;
+
+        let counters = infinispan_client().counter_configs().await.unwrap();
+
+        assert_eq!(2, counters.len());
+
+        match &counters["weak_counter"] {
+            Counter::Weak(counter) => assert_eq!(Some(32), counter.concurrency_level()),
+            Counter::Strong(_) => panic!("expected a weak counter"),
+        }
+
+        match &counters["strong_counter"] {
+            Counter::Strong(counter) => assert_eq!(Some(10), counter.upper_bound()),
+            Counter::Weak(_) => panic!("expected a strong counter"),
         }
     }
 
-    async fn counter_names_from_list_resp(response: Response) -> HashSet<String> {
-        serde_json::from_str(&read_body(response).await).unwrap()
+    async fn cleanup() {
+        for counter_name in run_typed(&counters::list()).await {
+            let _ = run(&counters::delete(counter_name)).await;
+        }
     }
 }
@@ -0,0 +1,155 @@
+mod helpers;
+
+#[cfg(test)]
+mod events {
+    use crate::helpers::*;
+    use futures_util::StreamExt;
+    use infinispan::errors::InfinispanError;
+    use infinispan::request::events::{self, Backoff, CacheEvent, CacheEventType};
+    use infinispan::request::{caches, entries, ToHttpRequest};
+    use serial_test::serial;
+    use std::time::{Duration, Instant};
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn fast_backoff() -> Backoff {
+        Backoff::default()
+            .with_initial_delay(Duration::from_millis(10))
+            .with_max_retries(1)
+    }
+
+    #[test]
+    fn listen_request() {
+        let http_req = events::listen("some_cache")
+            .include_current_state()
+            .to_http_req("http://localhost:11222", "Basic dXNlcm5hbWU6cGFzc3dvcmQ=");
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?action=listen&includeCurrentState=true",
+            http_req.uri().to_string()
+        );
+        assert_eq!("text/event-stream", http_req.headers()["accept"]);
+    }
+
+    #[tokio::test]
+    async fn parse_events_and_reconnect() {
+        let client = fake_server_with_responses(vec![
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+             : keep-alive\n\n\
+             event: cache-entry-created\ndata: some_key\n\n\
+             event: cache-entry-modified\r\ndata: {\"key\": \"other_key\", \"value\": \"v\"}\r\n\r\n\
+             event: unknown-event\ndata: ignored\n\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+             event: cache-entry-removed\ndata: some_key\n\n",
+        ])
+        .await;
+
+        let stream = events::subscribe(
+            &client,
+            events::listen("some_cache").with_backoff(fast_backoff()),
+        );
+        let results: Vec<_> = tokio::time::timeout(TIMEOUT, stream.collect())
+            .await
+            .unwrap();
+
+        let received: Vec<CacheEvent> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok().cloned())
+            .collect();
+
+        assert_eq!(
+            vec![
+                CacheEvent {
+                    event_type: CacheEventType::Created,
+                    key: "some_key".into(),
+                    value: None,
+                },
+                CacheEvent {
+                    event_type: CacheEventType::Modified,
+                    key: "other_key".into(),
+                    value: Some("v".into()),
+                },
+                CacheEvent {
+                    event_type: CacheEventType::Removed,
+                    key: "some_key".into(),
+                    value: None,
+                },
+            ],
+            received
+        );
+
+        // The last reconnection fails and ends the stream.
+        assert_eq!(4, results.len());
+        assert!(matches!(results[3], Err(InfinispanError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn reset_backoff_on_connect() {
+        const EMPTY_STREAM: &str =
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+
+        let client = fake_server_with_responses(vec![EMPTY_STREAM; 3]).await;
+
+        let backoff = Backoff::default()
+            .with_initial_delay(Duration::from_millis(200))
+            .with_max_retries(1);
+        let stream = events::subscribe(&client, events::listen("some_cache").with_backoff(backoff));
+
+        let start = Instant::now();
+        let results: Vec<_> = tokio::time::timeout(TIMEOUT, stream.collect())
+            .await
+            .unwrap();
+
+        // Each reconnection waits the initial delay, instead of doubling it
+        // (200ms + 400ms + 800ms).
+        assert!(start.elapsed() < Duration::from_millis(1_100));
+        assert_eq!(1, results.len());
+        assert!(matches!(results[0], Err(InfinispanError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn end_on_client_errors() {
+        let client =
+            fake_server_with_responses(vec!["HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"])
+                .await;
+
+        let stream = events::subscribe(&client, events::listen("missing_cache"));
+        let results: Vec<_> = tokio::time::timeout(TIMEOUT, stream.collect())
+            .await
+            .unwrap();
+
+        assert_eq!(1, results.len());
+        assert!(matches!(
+            results[0],
+            Err(InfinispanError::UnexpectedResponse { .. })
+        ));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn receive_cache_events() {
+        let cache_name = "test_events_cache";
+        let _ = run(&caches::delete(cache_name)).await;
+        let _ = run(&caches::create_local(cache_name)).await;
+
+        let mut stream = Box::pin(events::subscribe(
+            &infinispan_client(),
+            events::listen(cache_name),
+        ));
+
+        // Give the server some time to register the listener.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let _ = run(&entries::create(cache_name, "some_entry").with_value("a_value".into())).await;
+
+        let event = tokio::time::timeout(TIMEOUT, stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(CacheEventType::Created, event.event_type);
+        assert_eq!("some_entry", event.key);
+
+        let _ = run(&caches::delete(cache_name)).await;
+    }
+}
@@ -1,6 +1,12 @@
-use infinispan::request::ToHttpRequest;
+// Each test file only uses some of these helpers.
+#![allow(dead_code)]
+
+use infinispan::request::{ParseResponse, ToHttpRequest};
 use infinispan::Infinispan;
 use reqwest::Response;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
 
 pub fn infinispan_client() -> Infinispan {
     Infinispan::new("http://localhost:11222", "username", "password")
@@ -10,6 +16,82 @@ pub async fn run<R: ToHttpRequest>(request: &R) -> Response {
     infinispan_client().run(request).await.unwrap()
 }
 
+pub async fn run_typed<R: ToHttpRequest + ParseResponse>(request: &R) -> R::Output {
+    infinispan_client().run_typed(request).await.unwrap()
+}
+
 pub async fn read_body(response: Response) -> String {
     response.text_with_charset("utf-8").await.unwrap()
 }
+
+/// Response of the fake server to a request.
+pub enum FakeResponse {
+    /// Sent as is, including the status line and the headers.
+    Raw(String),
+    /// Sends the status line and the headers, and then each chunk received
+    /// until the sender is dropped.
+    Stream(&'static str, mpsc::UnboundedReceiver<&'static str>),
+}
+
+/// Starts an HTTP server that answers each connection with the response
+/// returned by `respond` for the head of its request. When `respond` returns
+/// `None`, it closes the connection and stops accepting new ones.
+pub async fn fake_server<F>(mut respond: F) -> Infinispan
+where
+    F: FnMut(&str) -> Option<FakeResponse> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match socket.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buf[..read]),
+                }
+            }
+
+            let response = match respond(&String::from_utf8_lossy(&request)) {
+                Some(response) => response,
+                None => return,
+            };
+
+            tokio::spawn(async move {
+                match response {
+                    FakeResponse::Raw(response) => {
+                        let _ = socket.write_all(response.as_bytes()).await;
+                    }
+                    FakeResponse::Stream(head, mut chunks) => {
+                        let _ = socket.write_all(head.as_bytes()).await;
+
+                        while let Some(chunk) = chunks.recv().await {
+                            let _ = socket.write_all(chunk.as_bytes()).await;
+                        }
+                    }
+                }
+
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+
+    Infinispan::new(format!("http://{}", addr), "username", "password")
+}
+
+/// A fake server that sends the given responses in order, one per
+/// connection, and then stops accepting connections.
+pub async fn fake_server_with_responses(responses: Vec<&'static str>) -> Infinispan {
+    let mut responses = responses.into_iter();
+
+    fake_server(move |_| {
+        responses
+            .next()
+            .map(|response| FakeResponse::Raw(response.into()))
+    })
+    .await
+}
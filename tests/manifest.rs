@@ -0,0 +1,202 @@
+#![cfg(feature = "manifest")]
+
+mod helpers;
+
+#[cfg(test)]
+mod manifest {
+    use crate::helpers::*;
+    use infinispan::errors::InfinispanError;
+    use infinispan::manifest::{self, Manifest, ResourceKind, ResourceStatus};
+    use infinispan::request::caches::Cache;
+    use infinispan::request::counters::{CounterStorage, CounterType};
+    use infinispan::request::{caches, counters, schemas};
+    use serial_test::serial;
+
+    const YAML_MANIFEST: &str = r#"
+schemas:
+  - name: test_manifest.proto
+    content: "package test;\nmessage Item { optional string id = 1; }\n"
+caches:
+  - name: test_manifest_cache
+    configuration:
+      distributed-cache:
+        mode: SYNC
+        memory:
+          max-count: 1000
+counters:
+  - name: test_manifest_counter
+    type: strong
+    initial-value: 5
+    lower-bound: 0
+    upper-bound: 100
+"#;
+
+    const TOML_MANIFEST: &str = r#"
+[[caches]]
+name = "test_manifest_cache"
+
+[caches.configuration.distributed-cache]
+mode = "SYNC"
+
+[caches.configuration.distributed-cache.memory]
+max-count = 1000
+
+[[counters]]
+name = "test_manifest_counter"
+type = "weak"
+storage = "PERSISTENT"
+concurrency-level = 16
+"#;
+
+    #[test]
+    fn parse_yaml() {
+        let manifest = Manifest::from_yaml(YAML_MANIFEST).unwrap();
+
+        assert_eq!(1, manifest.schemas.len());
+        assert!(matches!(
+            manifest.caches[0].configuration,
+            Cache::Distributed(_)
+        ));
+        assert_eq!(CounterType::Strong, manifest.counters[0].counter_type);
+        assert_eq!(Some(100), manifest.counters[0].upper_bound);
+        assert!(manifest.validate().is_ok());
+    }
+
+    #[test]
+    fn parse_toml() {
+        let manifest = Manifest::from_toml(TOML_MANIFEST).unwrap();
+
+        assert!(manifest.schemas.is_empty());
+        assert_eq!("test_manifest_cache", manifest.caches[0].name);
+        assert_eq!(CounterType::Weak, manifest.counters[0].counter_type);
+        assert_eq!(
+            Some(CounterStorage::Persistent),
+            manifest.counters[0].storage
+        );
+        assert_eq!(Some(16), manifest.counters[0].concurrency_level);
+        assert!(manifest.validate().is_ok());
+    }
+
+    #[test]
+    fn weak_counters_cannot_have_bounds() {
+        let manifest = Manifest::from_yaml(
+            "counters:\n  - name: some_counter\n    type: weak\n    upper-bound: 10\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            manifest.validate(),
+            Err(InfinispanError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn strong_counters_cannot_have_concurrency_level() {
+        let manifest = Manifest::from_yaml(
+            "counters:\n  - name: some_counter\n    type: strong\n    concurrency-level: 16\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            manifest.validate(),
+            Err(InfinispanError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn initial_value_must_be_within_bounds() {
+        let manifest = Manifest::from_yaml(
+            "counters:\n  - name: some_counter\n    type: strong\n    initial-value: 20\n    upper-bound: 10\n",
+        )
+        .unwrap();
+
+        // Checked by the request that creates the counter.
+        assert!(matches!(
+            manifest.validate(),
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+    }
+
+    async fn cleanup() {
+        let _ = run(&caches::delete("test_manifest_cache")).await;
+        let _ = run(&counters::delete("test_manifest_counter")).await;
+        let _ = run(&schemas::delete("test_manifest.proto")).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn apply_is_idempotent() {
+        cleanup().await;
+
+        let client = infinispan_client();
+        let manifest = Manifest::from_yaml(YAML_MANIFEST).unwrap();
+
+        let report = manifest::apply(&client, &manifest).await.unwrap();
+        assert!(report
+            .resources
+            .iter()
+            .all(|resource| resource.status == ResourceStatus::Created));
+
+        let report = manifest::apply(&client, &manifest).await.unwrap();
+        assert!(report
+            .resources
+            .iter()
+            .all(|resource| resource.status == ResourceStatus::Unchanged));
+
+        cleanup().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn apply_checks_indexed_entities() {
+        cleanup().await;
+
+        let client = infinispan_client();
+        let manifest = Manifest::from_yaml(
+            r#"
+caches:
+  - name: test_manifest_cache
+    configuration:
+      local-cache:
+        encoding:
+          media-type: application/x-protostream
+        indexing:
+          enabled: true
+          indexed-entities: [test.Missing]
+"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            manifest::apply(&client, &manifest).await,
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+        assert_eq!(
+            404,
+            run(&caches::exists("test_manifest_cache")).await.status()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn apply_reports_drifted_counters() {
+        cleanup().await;
+
+        let client = infinispan_client();
+        let mut manifest = Manifest::from_yaml(YAML_MANIFEST).unwrap();
+        manifest::apply(&client, &manifest).await.unwrap();
+
+        manifest.counters[0].upper_bound = Some(50);
+        let report = manifest::apply(&client, &manifest).await.unwrap();
+
+        let counter = report
+            .resources
+            .iter()
+            .find(|resource| resource.kind == ResourceKind::Counter)
+            .unwrap();
+        assert!(matches!(counter.status, ResourceStatus::Drifted(_)));
+        assert!(report.has_drift());
+
+        cleanup().await;
+    }
+}
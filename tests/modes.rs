@@ -0,0 +1,534 @@
+// These tests only check how the cache configuration types are built,
+// validated and serialized, so they do not need Infinispan running.
+
+#[cfg(test)]
+mod modes {
+    use infinispan::request::caches::modes::*;
+    use infinispan::request::caches::Cache;
+    use serde_json::json;
+
+    #[test]
+    fn parse_memory_size() {
+        assert_eq!(100_000_000, "100MB".parse::<MemorySize>().unwrap().bytes());
+        assert_eq!(
+            1_500_000_000,
+            "1.5GB".parse::<MemorySize>().unwrap().bytes()
+        );
+        assert_eq!(512 * 1024, "512KiB".parse::<MemorySize>().unwrap().bytes());
+        assert_eq!(1024, "1024".parse::<MemorySize>().unwrap().bytes());
+        assert!("100XB".parse::<MemorySize>().is_err());
+        assert!("MB".parse::<MemorySize>().is_err());
+    }
+
+    #[test]
+    fn format_memory_size() {
+        assert_eq!("100MB", MemorySize::from_bytes(100_000_000).to_string());
+        assert_eq!(
+            "2GiB",
+            MemorySize::from_bytes(2 * 1024 * 1024 * 1024).to_string()
+        );
+        assert_eq!("1001", MemorySize::from_bytes(1001).to_string());
+    }
+
+    #[test]
+    fn serialize_memory() {
+        let cache = Cache::Local(
+            Local::default().with_memory(
+                Memory::default()
+                    .with_storage(StorageType::OffHeap)
+                    .with_max_size("100MB".parse().unwrap())
+                    .with_when_full(EvictionStrategy::Remove),
+            ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({"storage": "OFF_HEAP", "max-size": "100MB", "when-full": "REMOVE"}),
+            serialized["local-cache"]["memory"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn validate_memory() {
+        let bounded_by_count = Cache::Distributed(
+            Distributed::create_sync().with_memory(Memory::default().with_max_count(1_000)),
+        );
+        assert!(bounded_by_count.validate().is_ok());
+
+        let size_on_heap = Cache::Distributed(
+            Distributed::create_sync()
+                .with_memory(Memory::default().with_max_size(MemorySize::from_bytes(1_000))),
+        );
+        assert!(size_on_heap.validate().is_err());
+
+        let count_and_size = Cache::Local(
+            Local::default().with_memory(
+                Memory::default()
+                    .with_storage(StorageType::OffHeap)
+                    .with_max_count(1_000)
+                    .with_max_size(MemorySize::from_bytes(1_000)),
+            ),
+        );
+        assert!(count_and_size.validate().is_err());
+    }
+
+    #[test]
+    fn serialize_encoding() {
+        let cache = Cache::Distributed(
+            Distributed::create_sync().with_encoding(
+                Encoding::default()
+                    .with_key(MEDIA_TYPE_PROTOSTREAM)
+                    .with_value(MEDIA_TYPE_JSON),
+            ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({
+                "key": {"media-type": "application/x-protostream"},
+                "value": {"media-type": "application/json"}
+            }),
+            serialized["distributed-cache"]["encoding"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn validate_encoding() {
+        let size_on_heap_with_protostream = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::protostream())
+                .with_memory(Memory::default().with_max_size("10MB".parse().unwrap())),
+        );
+        assert!(size_on_heap_with_protostream.validate().is_ok());
+
+        let size_on_heap_with_objects = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::new(MEDIA_TYPE_JAVA_OBJECT))
+                .with_memory(Memory::default().with_max_size("10MB".parse().unwrap())),
+        );
+        assert!(size_on_heap_with_objects.validate().is_err());
+
+        let size_on_heap_with_typed_objects = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::new(
+                    "Application/X-Java-Object ; type=java.lang.String",
+                ))
+                .with_memory(Memory::default().with_max_size("10MB".parse().unwrap())),
+        );
+        assert!(size_on_heap_with_typed_objects.validate().is_err());
+
+        let invalid_media_type =
+            Cache::Local(Local::default().with_encoding(Encoding::new("protostream")));
+        assert!(invalid_media_type.validate().is_err());
+    }
+
+    #[test]
+    fn serialize_persistence() {
+        let cache = Cache::Distributed(
+            Distributed::create_sync().with_persistence(
+                Persistence::default()
+                    .with_passivation(false)
+                    .with_jdbc_store(
+                        JdbcStore::default()
+                            .with_dialect("POSTGRES")
+                            .with_connection_pool(
+                                ConnectionPool::new(
+                                    "jdbc:postgresql://localhost/infinispan",
+                                    "org.postgresql.Driver",
+                                )
+                                .with_credentials("user", "secret"),
+                            )
+                            .with_table(StringKeyedTable::new(
+                                "ISPN",
+                                TableColumn::new("ID", "VARCHAR(255)"),
+                                TableColumn::new("DATA", "BYTEA"),
+                                TableColumn::new("TS", "BIGINT"),
+                            ))
+                            .with_shared(true)
+                            .with_write_behind(
+                                WriteBehind::default().with_modification_queue_size(1024),
+                            ),
+                    ),
+            ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({
+                "passivation": false,
+                "string-keyed-jdbc-store": {
+                    "shared": true,
+                    "write-behind": {"modification-queue-size": 1024},
+                    "dialect": "POSTGRES",
+                    "connection-pool": {
+                        "connection-url": "jdbc:postgresql://localhost/infinispan",
+                        "driver": "org.postgresql.Driver",
+                        "username": "user",
+                        "password": "secret"
+                    },
+                    "string-keyed-table": {
+                        "prefix": "ISPN",
+                        "id-column": {"name": "ID", "type": "VARCHAR(255)"},
+                        "data-column": {"name": "DATA", "type": "BYTEA"},
+                        "timestamp-column": {"name": "TS", "type": "BIGINT"}
+                    }
+                }
+            }),
+            serialized["distributed-cache"]["persistence"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn validate_persistence() {
+        let file_store = Cache::Local(Local::default().with_persistence(
+            Persistence::default().with_file_store(FileStore::default().with_preload(true)),
+        ));
+        assert!(file_store.validate().is_ok());
+
+        let passivation_with_shared_store = Cache::Local(
+            Local::default().with_persistence(
+                Persistence::default()
+                    .with_passivation(true)
+                    .with_jdbc_store(
+                        JdbcStore::default()
+                            .with_data_source("java:/datasources/ispn")
+                            .with_shared(true),
+                    ),
+            ),
+        );
+        assert!(passivation_with_shared_store.validate().is_err());
+
+        let jdbc_store_without_connection = Cache::Local(
+            Local::default()
+                .with_persistence(Persistence::default().with_jdbc_store(JdbcStore::default())),
+        );
+        assert!(jdbc_store_without_connection.validate().is_err());
+
+        let remote_store_without_servers = Cache::Local(Local::default().with_persistence(
+            Persistence::default().with_remote_store(RemoteStore::new("remote_cache")),
+        ));
+        assert!(remote_store_without_servers.validate().is_err());
+    }
+
+    #[test]
+    fn serialize_transaction() {
+        let cache = Cache::Replicated(
+            Replicated::create_sync()
+                .with_isolation(IsolationLevel::RepeatableRead)
+                .with_transaction(
+                    Transaction::new(TransactionMode::NonXa).with_locking(LockingMode::Pessimistic),
+                ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({"mode": "NON_XA", "locking": "PESSIMISTIC"}),
+            serialized["replicated-cache"]["transaction"]
+        );
+        assert_eq!(
+            "REPEATABLE_READ",
+            serialized["replicated-cache"]["locking"]["isolation"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn deserialize_isolation_levels() {
+        for (name, isolation) in [
+            ("NONE", IsolationLevel::None),
+            ("READ_UNCOMMITTED", IsolationLevel::ReadUncommitted),
+            ("READ_COMMITTED", IsolationLevel::ReadCommitted),
+            ("REPEATABLE_READ", IsolationLevel::RepeatableRead),
+            ("SERIALIZABLE", IsolationLevel::Serializable),
+        ] {
+            let cache: Cache =
+                serde_json::from_value(json!({"local-cache": {"locking": {"isolation": name}}}))
+                    .unwrap();
+
+            assert_eq!(
+                Cache::Local(
+                    serde_json::from_value::<Local>(json!({"locking": {}}))
+                        .unwrap()
+                        .with_isolation(isolation)
+                ),
+                cache
+            );
+        }
+    }
+
+    #[test]
+    fn validate_transaction() {
+        let sync_invalidation = Cache::Invalidation(
+            Invalidation::create_sync().with_transaction(Transaction::new(TransactionMode::FullXa)),
+        );
+        assert!(sync_invalidation.validate().is_ok());
+
+        let async_invalidation = Cache::Invalidation(
+            Invalidation::create_async()
+                .with_transaction(Transaction::new(TransactionMode::FullXa)),
+        );
+        assert!(async_invalidation.validate().is_err());
+
+        let locking_without_transactions = Cache::Local(Local::default().with_transaction(
+            Transaction::new(TransactionMode::None).with_locking(LockingMode::Optimistic),
+        ));
+        assert!(locking_without_transactions.validate().is_err());
+    }
+
+    #[test]
+    fn serialize_backups() {
+        let cache = Cache::Distributed(
+            Distributed::create_sync().with_backups(
+                Backups::default().with_site(
+                    "NYC",
+                    Backup::new(BackupStrategy::Sync)
+                        .with_failure_policy(BackupFailurePolicy::Warn)
+                        .with_take_offline(
+                            TakeOffline::default()
+                                .with_after_failures(3)
+                                .with_min_wait(60_000),
+                        ),
+                ),
+            ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({
+                "NYC": {
+                    "backup": {
+                        "strategy": "SYNC",
+                        "failure-policy": "WARN",
+                        "take-offline": {"after-failures": 3, "min-wait": 60000}
+                    }
+                }
+            }),
+            serialized["distributed-cache"]["backups"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn deserialize_backups_with_attributes() {
+        let backups: Backups = serde_json::from_value(json!({
+            "merge-policy": "PREFER_NON_NULL",
+            "max-cleanup-delay": 30000,
+            "tombstone-map-size": 512000,
+            "some-new-attribute": true,
+            "NYC": {"backup": {"strategy": "ASYNC"}}
+        }))
+        .unwrap();
+
+        assert_eq!(Some("PREFER_NON_NULL"), backups.merge_policy());
+        assert_eq!(Some(30000), backups.max_cleanup_delay());
+        assert_eq!(Some(512000), backups.tombstone_map_size());
+        assert_eq!(
+            Some(&Backup::new(BackupStrategy::Async)),
+            backups.site("NYC")
+        );
+        assert_eq!(None, backups.site("some-new-attribute"));
+
+        let serialized = json!(backups);
+        assert_eq!(json!(true), serialized["some-new-attribute"]);
+        assert_eq!(backups, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn validate_backups() {
+        let async_with_fail_policy = Cache::Replicated(Replicated::create_sync().with_backups(
+            Backups::default().with_site(
+                "LON",
+                Backup::new(BackupStrategy::Async).with_failure_policy(BackupFailurePolicy::Fail),
+            ),
+        ));
+
+        assert!(async_with_fail_policy.validate().is_err());
+    }
+
+    #[test]
+    fn serialize_partition_handling() {
+        let cache = Cache::Distributed(
+            Distributed::create_sync().with_partition_handling(
+                PartitionHandling::default()
+                    .with_when_split(WhenSplit::DenyReadWrites)
+                    .with_merge_policy(MergePolicy::PreferredNonNull),
+            ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({"when-split": "DENY_READ_WRITES", "merge-policy": "PREFERRED_NON_NULL"}),
+            serialized["distributed-cache"]["partition-handling"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+
+        let custom: PartitionHandling =
+            serde_json::from_value(json!({"merge-policy": "com.example.CustomMergePolicy"}))
+                .unwrap();
+
+        assert_eq!(
+            PartitionHandling::default()
+                .with_merge_policy(MergePolicy::Custom("com.example.CustomMergePolicy".into())),
+            custom
+        );
+    }
+
+    #[test]
+    fn serialize_indexing() {
+        let cache = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::protostream())
+                .with_indexing(
+                    Indexing::new(["sample.Person"])
+                        .with_indexed_entity("sample.Address")
+                        .with_storage(IndexStorage::Filesystem)
+                        .with_path("/data/indexes"),
+                ),
+        );
+
+        let serialized = json!(cache);
+
+        assert_eq!(
+            json!({
+                "enabled": true,
+                "storage": "filesystem",
+                "path": "/data/indexes",
+                "indexed-entities": ["sample.Person", "sample.Address"]
+            }),
+            serialized["local-cache"]["indexing"]
+        );
+        assert_eq!(cache, serde_json::from_value(serialized).unwrap());
+        assert!(cache.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_indexing() {
+        let without_protostream =
+            Cache::Local(Local::default().with_indexing(Indexing::new(["sample.Person"])));
+
+        let without_entities = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::protostream())
+                .with_indexing(Indexing::default().with_enabled(true)),
+        );
+
+        let invalid_entity = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::protostream())
+                .with_indexing(Indexing::new(["sample Person"])),
+        );
+
+        let path_on_heap = Cache::Local(
+            Local::default()
+                .with_encoding(Encoding::protostream())
+                .with_indexing(
+                    Indexing::new(["sample.Person"])
+                        .with_storage(IndexStorage::LocalHeap)
+                        .with_path("/data/indexes"),
+                ),
+        );
+
+        let disabled = Cache::Local(
+            Local::default().with_indexing(Indexing::new(["sample.Person"]).with_enabled(false)),
+        );
+
+        assert!(without_protostream.validate().is_err());
+        assert!(without_entities.validate().is_err());
+        assert!(invalid_entity.validate().is_err());
+        assert!(path_on_heap.validate().is_err());
+        assert!(disabled.validate().is_ok());
+    }
+
+    #[test]
+    fn check_indexed_entities() {
+        let schema = r#"
+            // Some comment with message Fake
+            package sample;
+
+            /* @Indexed */
+            message Person {
+                optional string name = 1;
+
+                message Address {
+                    optional string city = 1;
+                }
+
+                optional Address address = 2;
+            }
+        "#;
+
+        let indexing = Indexing::new(["sample.Person", "sample.Person.Address"]);
+        assert!(indexing.check_entities(&[schema]).is_ok());
+
+        let indexing = Indexing::new(["sample.Fake"]);
+        assert!(indexing.check_entities(&[schema]).is_err());
+    }
+
+    #[test]
+    fn round_trip_unknown_attributes() {
+        let server_config = json!({
+            "distributed-cache": {
+                "mode": "SYNC",
+                "owners": 2,
+                "statistics": true,
+                "statistics-available": false,
+                "encoding": {
+                    "key": {"media-type": "application/x-protostream"},
+                    "value": {"media-type": "application/x-protostream"}
+                },
+                "memory": {"storage": "HEAP", "max-count": 100, "when-full": "REMOVE"},
+                "expiration": {"lifespan": 60000, "max-idle": -1},
+                "locking": {"isolation": "REPEATABLE_READ", "acquire-timeout": 10000}
+            }
+        });
+
+        let cache: Cache = serde_json::from_value(server_config.clone()).unwrap();
+
+        match &cache {
+            Cache::Distributed(distributed) => {
+                assert_eq!(2, distributed.other_attributes()["owners"]);
+                assert!(distributed.other_attributes().contains_key("expiration"));
+            }
+            _ => panic!("expected a distributed cache"),
+        }
+
+        assert_eq!(server_config, json!(cache));
+    }
+
+    #[test]
+    fn modify_keeps_unknown_attributes() {
+        let server_config = json!({
+            "local-cache": {
+                "statistics": true,
+                "expiration": {"lifespan": 60000}
+            }
+        });
+
+        let cache = match serde_json::from_value(server_config).unwrap() {
+            Cache::Local(local) => {
+                Cache::Local(local.with_memory(Memory::default().with_max_count(1_000)))
+            }
+            _ => panic!("expected a local cache"),
+        };
+
+        assert_eq!(
+            json!({
+                "local-cache": {
+                    "statistics": true,
+                    "memory": {"max-count": 1000},
+                    "expiration": {"lifespan": 60000}
+                }
+            }),
+            json!(cache)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+mod helpers;
+
+#[cfg(test)]
+mod near_cache {
+    use crate::helpers::*;
+    use infinispan::errors::InfinispanError;
+    use infinispan::near_cache::NearCacheConfig;
+    use infinispan::request::{caches, entries};
+    use infinispan::Infinispan;
+    use serial_test::serial;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    // Serves the events of the first listen request from the returned
+    // sender, and closes them when it is dropped. Later listen requests
+    // fail. Each read of an entry returns a new value: "value-1",
+    // "value-2"...
+    async fn fake_cache_server() -> (Infinispan, mpsc::UnboundedSender<&'static str>) {
+        let (events_tx, events_rx) = mpsc::unbounded_channel::<&'static str>();
+        let mut events_rx = Some(events_rx);
+        let mut reads = 0;
+
+        let client = fake_server(move |request| {
+            if !request.contains("action=listen") {
+                reads += 1;
+                let value = format!("value-{}", reads);
+
+                return Some(FakeResponse::Raw(format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    value.len(),
+                    value
+                )));
+            }
+
+            Some(match events_rx.take() {
+                Some(events_rx) => FakeResponse::Stream(
+                    "HTTP/1.1 200 OK\r\n\
+                     Content-Type: text/event-stream\r\n\
+                     Connection: close\r\n\r\n",
+                    events_rx,
+                ),
+                None => FakeResponse::Raw(
+                    "HTTP/1.1 503 Service Unavailable\r\n\
+                     Content-Length: 0\r\nConnection: close\r\n\r\n"
+                        .into(),
+                ),
+            })
+        })
+        .await;
+
+        (client, events_tx)
+    }
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        tokio::time::timeout(TIMEOUT, async {
+            while !condition() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    // Reads the entry until it is returned from the near cache, which
+    // happens once the event stream is connected.
+    async fn read_until_cached(client: &Infinispan, entry_name: &str) -> String {
+        tokio::time::timeout(TIMEOUT, async {
+            loop {
+                let hits = client.near_cache_metrics("some_cache").unwrap().hits;
+                let value = client.get_entry("some_cache", entry_name).await.unwrap();
+
+                if client.near_cache_metrics("some_cache").unwrap().hits > hits {
+                    return value.unwrap();
+                }
+
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn invalidate_on_events_and_flush_on_disconnect() {
+        let (client, events_tx) = fake_cache_server().await;
+        let client = client
+            .with_near_cache("some_cache", NearCacheConfig::new(10))
+            .unwrap();
+
+        let cached = read_until_cached(&client, "some_entry").await;
+        assert_eq!(
+            Some(cached.clone()),
+            client.get_entry("some_cache", "some_entry").await.unwrap()
+        );
+
+        events_tx
+            .send("event: cache-entry-modified\ndata: some_entry\n\n")
+            .unwrap();
+        wait_until(|| {
+            client
+                .near_cache_metrics("some_cache")
+                .unwrap()
+                .invalidations
+                == 1
+        })
+        .await;
+
+        let updated = client.get_entry("some_cache", "some_entry").await.unwrap();
+        assert_ne!(Some(cached), updated);
+        assert_eq!(
+            updated,
+            client.get_entry("some_cache", "some_entry").await.unwrap()
+        );
+
+        drop(events_tx);
+        wait_until(|| client.near_cache_metrics("some_cache").unwrap().flushes == 1).await;
+
+        // Without events, every read goes to the server.
+        let misses = client.near_cache_metrics("some_cache").unwrap().misses;
+        let first = client.get_entry("some_cache", "some_entry").await.unwrap();
+        let second = client.get_entry("some_cache", "some_entry").await.unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(
+            misses + 2,
+            client.near_cache_metrics("some_cache").unwrap().misses
+        );
+    }
+
+    #[tokio::test]
+    async fn evict_least_recently_used() {
+        let (client, _events_tx) = fake_cache_server().await;
+        let client = client
+            .with_near_cache("some_cache", NearCacheConfig::new(1))
+            .unwrap();
+
+        read_until_cached(&client, "some_entry").await;
+        let _ = client.get_entry("some_cache", "other_entry").await.unwrap();
+
+        let metrics = client.near_cache_metrics("some_cache").unwrap();
+        assert_eq!(1, metrics.evictions);
+
+        let _ = client.get_entry("some_cache", "some_entry").await.unwrap();
+        assert_eq!(
+            metrics.misses + 1,
+            client.near_cache_metrics("some_cache").unwrap().misses
+        );
+    }
+
+    #[test]
+    fn fail_outside_a_runtime() {
+        let client = Infinispan::new("http://localhost:11222", "username", "password");
+
+        assert!(matches!(
+            client.with_near_cache("some_cache", NearCacheConfig::new(10)),
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn no_metrics_without_near_cache() {
+        let (client, _events_tx) = fake_cache_server().await;
+
+        assert_eq!(None, client.near_cache_metrics("some_cache"));
+        assert_eq!(
+            Some("value-1".to_string()),
+            client.get_entry("some_cache", "some_entry").await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn read_entries_from_near_cache() {
+        let cache_name = "test_near_cache";
+        let _ = run(&caches::delete(cache_name)).await;
+        let _ = run(&caches::create_local(cache_name)).await;
+        let _ = run(&entries::create(cache_name, "some_entry").with_value("a_value".into())).await;
+
+        let client = infinispan_client()
+            .with_near_cache(cache_name, NearCacheConfig::new(10))
+            .unwrap();
+
+        // Give the server some time to register the listener.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        for _ in 0..2 {
+            assert_eq!(
+                Some("a_value".to_string()),
+                client.get_entry(cache_name, "some_entry").await.unwrap()
+            );
+        }
+        assert_eq!(1, client.near_cache_metrics(cache_name).unwrap().hits);
+
+        let _ = run(&entries::update(cache_name, "some_entry", "new_value")).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert_eq!(
+            Some("new_value".to_string()),
+            client.get_entry(cache_name, "some_entry").await.unwrap()
+        );
+        assert_eq!(
+            None,
+            client.get_entry(cache_name, "missing_entry").await.unwrap()
+        );
+
+        let _ = run(&caches::delete(cache_name)).await;
+    }
+}
@@ -0,0 +1,270 @@
+mod helpers;
+
+#[cfg(test)]
+mod query {
+    use crate::helpers::*;
+    use futures_util::StreamExt;
+    use infinispan::request::caches::modes::*;
+    use infinispan::request::caches::Cache;
+    use infinispan::request::query::{field, param, Query};
+    use infinispan::request::{caches, entries, query, schemas};
+    use infinispan::Infinispan;
+    use serde::Deserialize;
+    use serde_json::json;
+    use serial_test::serial;
+
+    const CACHE_NAME: &str = "test_query_cache";
+    const SCHEMA_NAME: &str = "test_query.proto";
+    const SCHEMA: &str = "package test;\n\n\
+        message Person {\n  \
+          optional string name = 1;\n  \
+          optional int32 age = 2;\n\
+        }\n";
+
+    #[derive(Debug, Deserialize)]
+    struct Person {
+        name: String,
+        age: i32,
+    }
+
+    async fn setup(number_of_people: i32) {
+        let _ = run(&caches::delete(CACHE_NAME)).await;
+        let _ = run(&schemas::delete(SCHEMA_NAME)).await;
+
+        let _ = run(&schemas::create(SCHEMA_NAME, SCHEMA)).await;
+        let _ = run(&caches::create(
+            CACHE_NAME,
+            Cache::Local(Local::default().with_encoding(Encoding::protostream())),
+        ))
+        .await;
+
+        for i in 0..number_of_people {
+            let person = json!({"_type": "test.Person", "name": format!("person_{}", i), "age": i});
+            let _ = run(&entries::create(CACHE_NAME, format!("person_{}", i))
+                .with_value(person.to_string()))
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn search() {
+        setup(10).await;
+
+        let results = run_typed(
+            &query::search::<Person>(CACHE_NAME, "FROM test.Person WHERE age >= 5 ORDER BY age")
+                .with_max_results(3),
+        )
+        .await;
+
+        assert_eq!(5, results.hit_count);
+        assert_eq!(
+            vec![5, 6, 7],
+            results
+                .hits
+                .iter()
+                .map(|person| person.age)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("person_5", results.hits[0].name);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn search_with_builder() {
+        setup(10).await;
+
+        let query = Query::from("test.Person")
+            .where_(field("age").lt(param("max_age")))
+            .and(field("name").like("person_%"))
+            .order_by_desc("age")
+            .with_param("max_age", 3);
+
+        let results = run_typed(&query::search::<Person>(
+            CACHE_NAME,
+            query.to_ickle().unwrap(),
+        ))
+        .await;
+
+        assert_eq!(
+            vec![2, 1, 0],
+            results
+                .hits
+                .iter()
+                .map(|person| person.age)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn search_with_escaped_values() {
+        setup(2).await;
+
+        let name = r"O'Brien\' OR name != '\";
+        let person = json!({"_type": "test.Person", "name": name, "age": 40});
+        let _ = run(&entries::create(CACHE_NAME, "tricky").with_value(person.to_string())).await;
+
+        let query = Query::from("test.Person")
+            .where_(field("name").eq(param("name")))
+            .with_param("name", name);
+
+        let results = run_typed(&query::search::<Person>(
+            CACHE_NAME,
+            query.to_ickle().unwrap(),
+        ))
+        .await;
+
+        assert_eq!(1, results.hit_count);
+        assert_eq!(name, results.hits[0].name);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn paginate() {
+        setup(25).await;
+
+        let client = infinispan_client();
+        let request = query::search::<Person>(CACHE_NAME, "FROM test.Person ORDER BY age");
+
+        let mut paginator = query::paginate(&client, request, 10);
+        let mut page_sizes = Vec::new();
+
+        while let Some(page) = paginator.next_page().await.unwrap() {
+            page_sizes.push(page.len());
+        }
+
+        assert_eq!(vec![10, 10, 5], page_sizes);
+    }
+
+    // Serves pages with the given ages, like a server that returns at most
+    // two hits per page.
+    async fn fake_search_server(pages: Vec<(Vec<i32>, u64, bool)>) -> Infinispan {
+        let mut pages = pages.into_iter();
+
+        fake_server(move |_| {
+            let (ages, hit_count, hit_count_exact) = pages.next()?;
+            let hits: Vec<_> = ages
+                .into_iter()
+                .map(|age| json!({"hit": {"name": "someone", "age": age}}))
+                .collect();
+            let body = json!({
+                "hit_count": hit_count,
+                "hit_count_exact": hit_count_exact,
+                "hits": hits
+            })
+            .to_string();
+
+            Some(FakeResponse::Raw(format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn paginate_with_capped_pages() {
+        let client = fake_search_server(vec![
+            (vec![1, 2], 5, true),
+            (vec![3, 4], 5, true),
+            (vec![5], 5, true),
+        ])
+        .await;
+        let request = query::search::<Person>(CACHE_NAME, "FROM test.Person");
+
+        let ages: Vec<i32> = query::paginate(&client, request, 5)
+            .into_stream()
+            .map(|person| person.unwrap().age)
+            .collect()
+            .await;
+
+        assert_eq!(vec![1, 2, 3, 4, 5], ages);
+
+        let client = fake_search_server(vec![
+            (vec![1, 2], 2, false),
+            (vec![3], 3, false),
+            (vec![], 3, false),
+        ])
+        .await;
+        let request = query::search::<Person>(CACHE_NAME, "FROM test.Person");
+
+        let mut paginator = query::paginate(&client, request, 5);
+        let mut page_sizes = Vec::new();
+
+        while let Some(page) = paginator.next_page().await.unwrap() {
+            page_sizes.push(page.len());
+        }
+
+        assert_eq!(vec![2, 1], page_sizes);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn stream_hits() {
+        setup(25).await;
+
+        let client = infinispan_client();
+        let request = query::search::<Person>(CACHE_NAME, "FROM test.Person ORDER BY age")
+            .with_offset(3)
+            .with_max_results(15);
+
+        let ages: Vec<i32> = query::paginate(&client, request, 4)
+            .into_stream()
+            .map(|person| person.unwrap().age)
+            .collect()
+            .await;
+
+        assert_eq!((3..18).collect::<Vec<_>>(), ages);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delete_by_query() {
+        setup(10).await;
+
+        let statement = Query::from("test.Person")
+            .where_(field("age").lt(5))
+            .to_delete_statement()
+            .unwrap();
+
+        let resp = run(&query::delete_by_query(CACHE_NAME, statement)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&caches::size(CACHE_NAME)).await;
+        assert_eq!("5", read_body(resp).await);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn index_operations() {
+        setup(0).await;
+        let _ = run(&caches::delete(CACHE_NAME)).await;
+
+        let indexed_cache = Local::default()
+            .with_encoding(Encoding::protostream())
+            .with_indexing(Indexing::new(["test.Person"]).with_storage(IndexStorage::LocalHeap));
+        let _ = run(&caches::create(CACHE_NAME, Cache::Local(indexed_cache))).await;
+
+        let person = json!({"_type": "test.Person", "name": "person_0", "age": 0});
+        let _ = run(&entries::create(CACHE_NAME, "person_0").with_value(person.to_string())).await;
+
+        let stats = run_typed(&query::index_stats(CACHE_NAME)).await;
+        assert_eq!(1, stats.types["test.Person"].count);
+
+        let resp = run(&query::clear_indexes(CACHE_NAME)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&query::reindex(CACHE_NAME)).await;
+        assert!(resp.status().is_success());
+
+        let _ = run_typed(&query::search::<Person>(CACHE_NAME, "FROM test.Person")).await;
+
+        let stats = run_typed(&query::query_stats(CACHE_NAME)).await;
+        assert!(stats.indexed_local.is_some());
+
+        let resp = run(&query::reset_query_stats(CACHE_NAME)).await;
+        assert!(resp.status().is_success());
+    }
+}
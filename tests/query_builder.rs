@@ -0,0 +1,95 @@
+// These tests only check how queries are rendered, so they do not need
+// Infinispan running.
+
+#[cfg(test)]
+mod query_builder {
+    use infinispan::errors::InfinispanError;
+    use infinispan::request::query::{field, param, Query};
+
+    #[test]
+    fn render_conditions() {
+        let query = Query::from("sample.Person")
+            .select(["name", "age"])
+            .where_(field("age").gte(18).and(field("age").lt(65)))
+            .or(field("address.city").in_(["Barcelona", "Berlin"]))
+            .and(field("email").is_not_null().not())
+            .order_by_desc("age")
+            .order_by("name");
+
+        assert_eq!(
+            "SELECT name, age FROM sample.Person \
+             WHERE ((age >= 18 AND age < 65) OR address.city IN ('Barcelona', 'Berlin')) \
+             AND NOT email IS NOT NULL \
+             ORDER BY age DESC, name ASC",
+            query.to_ickle().unwrap()
+        );
+    }
+
+    #[test]
+    fn escape_string_literals() {
+        let query = Query::from("sample.Person").where_(field("name").eq("x' OR name != 'y\\"));
+
+        assert_eq!(
+            r"FROM sample.Person WHERE name = 'x'' OR name != ''y\\'",
+            query.to_ickle().unwrap()
+        );
+    }
+
+    #[test]
+    fn escape_named_params() {
+        let query = Query::from("sample.Person")
+            .where_(field("name").eq(param("name")))
+            .with_param("name", "O'Brien' OR '1' = '1");
+
+        assert_eq!(
+            "FROM sample.Person WHERE name = 'O''Brien'' OR ''1'' = ''1'",
+            query.to_ickle().unwrap()
+        );
+    }
+
+    #[test]
+    fn named_params() {
+        let query = Query::from("sample.Person")
+            .where_(field("name").like(param("pattern")))
+            .and(field("active").eq(param("active")))
+            .with_param("pattern", "J%")
+            .with_param("active", true);
+
+        assert_eq!(
+            "FROM sample.Person WHERE name LIKE 'J%' AND active = true",
+            query.to_ickle().unwrap()
+        );
+
+        let missing_param = Query::from("sample.Person").where_(field("age").gt(param("age")));
+
+        assert!(matches!(
+            missing_param.to_ickle(),
+            Err(InfinispanError::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_identifiers() {
+        let invalid_field = Query::from("sample.Person").where_(field("age > 0 OR 1").eq(1));
+        let invalid_entity = Query::from("sample.Person WHERE 1 = 1");
+        let invalid_float = Query::from("sample.Person").where_(field("score").gt(f64::NAN));
+
+        for query in [invalid_field, invalid_entity, invalid_float] {
+            assert!(matches!(
+                query.to_ickle(),
+                Err(InfinispanError::InvalidQuery(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn render_delete_statement() {
+        let query = Query::from("sample.Person").where_(field("age").lt(18));
+
+        assert_eq!(
+            "DELETE FROM sample.Person WHERE age < 18",
+            query.to_delete_statement().unwrap()
+        );
+        assert!(query.order_by("age").to_delete_statement().is_err());
+    }
+}
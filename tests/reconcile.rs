@@ -0,0 +1,205 @@
+mod helpers;
+
+#[cfg(test)]
+mod reconcile {
+    use crate::helpers::*;
+    use infinispan::errors::InfinispanError;
+    use infinispan::reconcile::{self, AttributeDiff, Reconciliation};
+    use infinispan::request::caches::modes::*;
+    use infinispan::request::caches::Cache;
+    use infinispan::request::{caches, schemas};
+    use serde_json::json;
+    use serial_test::serial;
+
+    const TEST_CACHE_NAME: &str = "test_reconcile_cache";
+
+    fn cache_with_max_count(max_count: i64) -> Cache {
+        Cache::Distributed(
+            Distributed::create_sync().with_memory(Memory::default().with_max_count(max_count)),
+        )
+    }
+
+    #[test]
+    fn diff_ignores_attributes_only_in_server() {
+        let expected = Cache::Local(Local::default());
+        let actual = Cache::Local(Local::default().with_other_attribute("owners", json!(2)));
+
+        assert!(reconcile::diff(&expected, &actual, &[]).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_mutable_attributes() {
+        let mutable_attributes = vec!["memory.max-count".to_string()];
+
+        let diffs = reconcile::diff(
+            &cache_with_max_count(1_000),
+            &cache_with_max_count(2_000),
+            &mutable_attributes,
+        );
+
+        assert_eq!(
+            vec![AttributeDiff {
+                attribute: "memory.max-count".into(),
+                expected: json!(1_000),
+                actual: Some(json!(2_000)),
+                mutable: true,
+            }],
+            diffs
+        );
+    }
+
+    #[test]
+    fn diff_reports_different_cache_types() {
+        let diffs = reconcile::diff(
+            &Cache::Local(Local::default()),
+            &Cache::Distributed(Distributed::create_sync()),
+            &[],
+        );
+
+        assert_eq!(1, diffs.len());
+        assert_eq!("type", diffs[0].attribute);
+        assert!(!diffs[0].mutable);
+    }
+
+    #[test]
+    fn diff_treats_strings_as_numbers() {
+        let expected = Cache::Local(Local::default().with_other_attribute("timeout", json!(10)));
+        let actual = Cache::Local(Local::default().with_other_attribute("timeout", json!("10")));
+
+        assert!(reconcile::diff(&expected, &actual, &[]).is_empty());
+    }
+
+    #[test]
+    fn diff_normalizes_values() {
+        let expected = Cache::Local(
+            Local::default()
+                .with_memory(Memory::default().with_max_size("1000KiB".parse().unwrap()))
+                .with_other_attribute("timeout", json!(10))
+                .with_other_attribute("statistics", json!(true)),
+        );
+        let actual = Cache::Local(
+            Local::default()
+                .with_other_attribute("memory", json!({"max-size": "1024000"}))
+                .with_other_attribute("timeout", json!("10.0"))
+                .with_other_attribute("statistics", json!("true")),
+        );
+
+        assert!(reconcile::diff(&expected, &actual, &[]).is_empty());
+
+        let actual = Cache::Local(
+            Local::default().with_memory(Memory::default().with_max_size("1MB".parse().unwrap())),
+        );
+        let expected = Cache::Local(
+            Local::default().with_memory(Memory::default().with_max_size("1MiB".parse().unwrap())),
+        );
+
+        assert_eq!(1, reconcile::diff(&expected, &actual, &[]).len());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn ensure_cache() {
+        let client = infinispan_client();
+        let _ = run(&caches::delete(TEST_CACHE_NAME)).await;
+
+        let created =
+            reconcile::ensure_cache(&client, TEST_CACHE_NAME, &cache_with_max_count(1_000))
+                .await
+                .unwrap();
+        assert_eq!(Reconciliation::Created, created);
+
+        let unchanged =
+            reconcile::ensure_cache(&client, TEST_CACHE_NAME, &cache_with_max_count(1_000))
+                .await
+                .unwrap();
+        assert_eq!(Reconciliation::Unchanged, unchanged);
+
+        let updated =
+            reconcile::ensure_cache(&client, TEST_CACHE_NAME, &cache_with_max_count(2_000))
+                .await
+                .unwrap();
+        assert!(matches!(updated, Reconciliation::Updated(diffs) if diffs.len() == 1));
+        assert_eq!(
+            cache_with_max_count(2_000),
+            run_typed(&caches::get_config(TEST_CACHE_NAME)).await
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn ensure_cache_with_immutable_changes() {
+        let client = infinispan_client();
+        let _ = run(&caches::delete(TEST_CACHE_NAME)).await;
+        let _ = run(&caches::create_distributed_sync(TEST_CACHE_NAME)).await;
+
+        let err = reconcile::ensure_cache(
+            &client,
+            TEST_CACHE_NAME,
+            &Cache::Distributed(Distributed::create_sync().with_encoding(Encoding::protostream())),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, InfinispanError::CacheConfigMismatch { .. }));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn ensure_cache_checks_indexed_entities() {
+        let client = infinispan_client();
+        let _ = run(&caches::delete(TEST_CACHE_NAME)).await;
+        let _ = run(&schemas::create(
+            "test_reconcile.proto",
+            "package test_reconcile;\nmessage Person { optional string name = 1; }",
+        ))
+        .await;
+
+        let indexed_cache = |entity: &str| {
+            Cache::Local(
+                Local::default()
+                    .with_encoding(Encoding::protostream())
+                    .with_indexing(Indexing::new([entity])),
+            )
+        };
+
+        let err = reconcile::ensure_cache(
+            &client,
+            TEST_CACHE_NAME,
+            &indexed_cache("test_reconcile.Missing"),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, InfinispanError::InvalidConfig(_)));
+
+        reconcile::check_indexed_entities(&client, &indexed_cache("test_reconcile.Person"))
+            .await
+            .unwrap();
+
+        let schemas = reconcile::registered_schemas(&client).await.unwrap();
+
+        let err = reconcile::ensure_cache_with_schemas(
+            &client,
+            TEST_CACHE_NAME,
+            &indexed_cache("test_reconcile.Missing"),
+            &schemas,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, InfinispanError::InvalidConfig(_)));
+
+        assert_eq!(
+            Reconciliation::Created,
+            reconcile::ensure_cache_with_schemas(
+                &client,
+                TEST_CACHE_NAME,
+                &indexed_cache("test_reconcile.Person"),
+                &schemas,
+            )
+            .await
+            .unwrap()
+        );
+
+        let _ = run(&caches::delete(TEST_CACHE_NAME)).await;
+        let _ = run(&schemas::delete("test_reconcile.proto")).await;
+    }
+}
@@ -0,0 +1,540 @@
+// These tests check how requests are converted to HTTP requests, so they do
+// not need Infinispan running.
+
+#[cfg(test)]
+mod request {
+    use http::header::{AUTHORIZATION, CONTENT_TYPE};
+    use http::StatusCode;
+    use infinispan::errors::InfinispanError;
+    use infinispan::request::caches::modes::Local;
+    use infinispan::request::caches::{Availability, Cache, CacheHealth, CacheMode, CacheStatus};
+    use infinispan::request::counters::{Counter, CounterStorage, CounterType};
+    use infinispan::request::{
+        caches, counters, entries, query, schemas, templates, ParseResponse, ToHttpRequest,
+    };
+    use serde_json::json;
+
+    const BASE_URL: &str = "http://localhost:11222";
+    const BASIC_AUTH: &str = "Basic dXNlcm5hbWU6cGFzc3dvcmQ=";
+
+    #[test]
+    fn json_content_type_by_default() {
+        let http_req = entries::update("some_cache", "some_entry", "some_value")
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!("application/json", http_req.headers()[CONTENT_TYPE]);
+        assert_eq!(BASIC_AUTH, http_req.headers()[AUTHORIZATION]);
+    }
+
+    #[test]
+    fn create_cache_from_xml() {
+        let xml = r#"<local-cache name="some_cache" statistics="true"/>"#;
+
+        let http_req = caches::create_from_xml("some_cache", xml).to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!(
+            vec!["application/xml"],
+            http_req
+                .headers()
+                .get_all(CONTENT_TYPE)
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache",
+            http_req.uri().to_string()
+        );
+        assert_eq!(xml, http_req.body());
+    }
+
+    #[test]
+    fn create_cache_from_yaml() {
+        let yaml = "localCache:\n  statistics: true\n";
+
+        let http_req =
+            caches::create_from_yaml("some_cache", yaml).to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!("application/yaml", http_req.headers()[CONTENT_TYPE]);
+        assert_eq!(yaml, http_req.body());
+    }
+
+    #[test]
+    fn create_cache_from_template() {
+        let http_req = caches::create_from_template("some_cache", "org.infinispan.DIST_SYNC")
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?template=org.infinispan.DIST_SYNC",
+            http_req.uri().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_json_response() {
+        let req = caches::get_config("some_cache");
+        let body = serde_json::to_string(&Cache::Local(Local::default())).unwrap();
+
+        let config = req.parse_response(StatusCode::OK, &body).unwrap();
+
+        assert_eq!(Cache::Local(Local::default()), config);
+    }
+
+    #[test]
+    fn parse_template_list() {
+        let body = r#"[
+            {"name": "org.infinispan.LOCAL", "configuration": {"local-cache-configuration": {}}},
+            {
+                "name": "org.infinispan.SCATTERED_SYNC",
+                "configuration": {"scattered-cache-configuration": {"mode": "SYNC"}}
+            }
+        ]"#;
+
+        let templates = templates::list()
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(2, templates.len());
+        assert_eq!("org.infinispan.LOCAL", templates[0].name);
+        assert!(matches!(templates[0].configuration, Cache::Local(_)));
+        assert_eq!(
+            Cache::Other(json!({"scattered-cache-configuration": {"mode": "SYNC"}})),
+            templates[1].configuration
+        );
+    }
+
+    #[test]
+    fn parse_error_response() {
+        let req = caches::get_config("non_existing");
+
+        let err = req
+            .parse_response(StatusCode::NOT_FOUND, "cache not found")
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            InfinispanError::UnexpectedResponse {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn set_mutable_attribute() {
+        let http_req = caches::set_mutable_attribute("some_cache", "expiration.lifespan", 60_000)
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!("POST", http_req.method());
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?action=set-mutable-attribute\
+             &attribute-name=expiration.lifespan&attribute-value=60000",
+            http_req.uri().to_string()
+        );
+    }
+
+    #[test]
+    fn compare_cache_configs() {
+        let req = caches::compare(
+            Cache::Local(Local::default()),
+            Cache::Local(Local::default()),
+        )
+        .ignore_mutable();
+
+        let http_req = req.to_http_req(BASE_URL, BASIC_AUTH);
+        let content_type = http_req.headers()[CONTENT_TYPE].to_str().unwrap();
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches?action=compare&ignoreMutable=true",
+            http_req.uri().to_string()
+        );
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        assert_eq!(2, http_req.body().matches("\"local-cache\"").count());
+
+        assert!(req.parse_response(StatusCode::NO_CONTENT, "").unwrap());
+        assert!(!req.parse_response(StatusCode::CONFLICT, "").unwrap());
+    }
+
+    #[test]
+    fn create_schema() {
+        let schema = "message Person { optional string name = 1; }";
+
+        let http_req = schemas::create("person.proto", schema).to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!("text/plain", http_req.headers()[CONTENT_TYPE]);
+        assert_eq!(
+            "http://localhost:11222/rest/v2/schemas/person.proto",
+            http_req.uri().to_string()
+        );
+        assert_eq!(schema, http_req.body());
+    }
+
+    #[test]
+    fn parse_cache_stats() {
+        let body = r#"{"time_since_start":120,"time_since_reset":60,"stores":3,"hits":3,"misses":1,"remove_hits":2,"remove_misses":1,"hit_ratio":0.75,"average_read_time_nanos":1500}"#;
+
+        let stats = caches::stats("some_cache")
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(120, stats.time_since_start);
+        assert_eq!(3, stats.removes());
+        assert_eq!(0.75, stats.hit_ratio);
+        assert_eq!(0, stats.evictions);
+    }
+
+    #[test]
+    fn parse_cache_info() {
+        let body = r#"{
+            "stats": {"time_since_start": 10, "hits": 2},
+            "size": 4,
+            "configuration": {"distributed-cache": {"mode": "SYNC", "owners": 2}},
+            "rehash_in_progress": false,
+            "bounded": true,
+            "indexed": false,
+            "persistent": false,
+            "transactional": true,
+            "secured": false,
+            "has_remote_backup": false,
+            "indexing_in_progress": false,
+            "statistics": true,
+            "key_storage": "application/x-protostream",
+            "value_storage": "application/x-protostream",
+            "mode": "DIST_SYNC"
+        }"#;
+
+        let info = caches::get("some_cache")
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(CacheMode::DistSync, info.mode);
+        assert!(matches!(info.configuration, Cache::Distributed(_)));
+        assert_eq!(2, info.stats.hits);
+        assert!(info.bounded && info.transactional);
+        assert_eq!("application/x-protostream", info.key_storage);
+        assert_eq!(Some(&json!(true)), info.other.get("statistics"));
+    }
+
+    #[test]
+    fn parse_cache_info_with_unknown_mode() {
+        let body = r#"{
+            "stats": {},
+            "size": 0,
+            "configuration": {"scattered-cache": {"mode": "SYNC"}},
+            "mode": "SOME_NEW_MODE"
+        }"#;
+
+        let info = caches::get("some_cache")
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(CacheMode::Unknown, info.mode);
+        assert_eq!(
+            Cache::Other(json!({"scattered-cache": {"mode": "SYNC"}})),
+            info.configuration
+        );
+    }
+
+    #[test]
+    fn fail_on_invalid_known_modes() {
+        let invalid = json!({
+            "local-cache-configuration": {"transaction": {"mode": "NON_XAA"}}
+        });
+
+        assert!(serde_json::from_value::<Cache>(invalid).is_err());
+    }
+
+    #[test]
+    fn parse_detailed_cache_list() {
+        let body = r#"[{
+            "status": "RUNNING",
+            "name": "some_cache",
+            "type": "distributed-cache",
+            "simple_cache": false,
+            "transactional": false,
+            "persistent": true,
+            "bounded": false,
+            "secured": false,
+            "indexed": false,
+            "has_remote_backup": false,
+            "health": "HEALTHY_REBALANCING",
+            "rebalancing_enabled": true
+        }]"#;
+
+        let req = caches::list_detailed();
+        let caches = req.parse_response(StatusCode::OK, body).unwrap();
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches?action=detailed",
+            req.to_http_req(BASE_URL, BASIC_AUTH).uri().to_string()
+        );
+        assert_eq!("some_cache", caches[0].name);
+        assert_eq!(CacheStatus::Running, caches[0].status);
+        assert_eq!(CacheHealth::HealthyRebalancing, caches[0].health);
+        assert!(caches[0].persistent && caches[0].rebalancing_enabled);
+    }
+
+    #[test]
+    fn cache_availability() {
+        let req = caches::get_availability("some_cache");
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?action=get-availability",
+            req.to_http_req(BASE_URL, BASIC_AUTH).uri().to_string()
+        );
+        assert_eq!(
+            Availability::DegradedMode,
+            req.parse_response(StatusCode::OK, "DEGRADED_MODE").unwrap()
+        );
+        assert!(req
+            .parse_response(StatusCode::OK, "SOMETHING_ELSE")
+            .is_err());
+
+        let http_req = caches::set_availability("some_cache", Availability::Available)
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?action=set-availability&availability=AVAILABLE",
+            http_req.uri().to_string()
+        );
+    }
+
+    #[test]
+    fn key_distribution() {
+        let req = caches::key_distribution("some_cache", "some key");
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/some_cache?action=distribution&key=some%20key",
+            req.to_http_req(BASE_URL, BASIC_AUTH).uri().to_string()
+        );
+
+        let body = r#"{
+            "contains_key": true,
+            "owners": [
+                {"node_name": "node-a", "primary": true, "node_addresses": ["10.0.0.1:7800"]},
+                {"node_name": "node-b", "primary": false, "node_addresses": ["10.0.0.2:7800"]}
+            ]
+        }"#;
+
+        let distribution = req.parse_response(StatusCode::OK, body).unwrap();
+
+        assert!(distribution.contains_key);
+        assert_eq!(
+            vec!["node-a"],
+            distribution
+                .owners
+                .iter()
+                .filter(|owner| owner.primary)
+                .map(|owner| owner.node_name.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn search() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Person {
+            name: String,
+        }
+
+        let req = query::search::<Person>("people", "FROM test.Person WHERE age > 30")
+            .with_offset(20)
+            .with_max_results(10);
+
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/people?action=search&query=FROM%20test.Person%20WHERE%20age%20%3E%2030&offset=20&max_results=10",
+            req.to_http_req(BASE_URL, BASIC_AUTH).uri().to_string()
+        );
+
+        let body = r#"{
+            "hit_count": 42,
+            "hit_count_exact": true,
+            "hits": [{"hit": {"_type": "test.Person", "name": "Alice"}}]
+        }"#;
+
+        let results = req.parse_response(StatusCode::OK, body).unwrap();
+
+        assert_eq!(42, results.hit_count);
+        assert!(results.hit_count_exact);
+        assert_eq!(
+            vec![Person {
+                name: "Alice".into()
+            }],
+            results.hits
+        );
+    }
+
+    #[test]
+    fn index_requests() {
+        let http_req = query::delete_by_query("people", "DELETE FROM test.Person WHERE age < 18")
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!("POST", http_req.method());
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/people?action=deleteByQuery&query=DELETE%20FROM%20test.Person%20WHERE%20age%20%3C%2018",
+            http_req.uri().to_string()
+        );
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/people/search/indexes?action=reindex",
+            query::reindex("people")
+                .to_http_req(BASE_URL, BASIC_AUTH)
+                .uri()
+                .to_string()
+        );
+        assert_eq!(
+            "http://localhost:11222/rest/v2/caches/people/search/query/stats?action=clear",
+            query::reset_query_stats("people")
+                .to_http_req(BASE_URL, BASIC_AUTH)
+                .uri()
+                .to_string()
+        );
+
+        let body = r#"{
+            "types": {"test.Person": {"count": 25, "size": 4096}},
+            "reindexing": false
+        }"#;
+
+        let stats = query::index_stats("people")
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(25, stats.types["test.Person"].count);
+        assert!(!stats.reindexing);
+    }
+
+    #[test]
+    fn create_counters() {
+        let http_req = counters::create_weak("some_counter")
+            .with_storage(CounterStorage::Persistent)
+            .with_concurrency_level(32)
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!(
+            json!({"weak-counter": {"storage": "PERSISTENT", "concurrency-level": 32}}),
+            serde_json::from_str::<serde_json::Value>(http_req.body()).unwrap()
+        );
+
+        let http_req = counters::create_strong("some_counter")
+            .with_value(5)
+            .with_lower_bound(0)
+            .with_upper_bound(10)
+            .to_http_req(BASE_URL, BASIC_AUTH);
+
+        assert_eq!(
+            json!({"strong-counter": {"initial-value": 5, "lower-bound": 0, "upper-bound": 10}}),
+            serde_json::from_str::<serde_json::Value>(http_req.body()).unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_counters() {
+        assert!(counters::create_strong("some_counter")
+            .with_value(5)
+            .with_upper_bound(10)
+            .validate()
+            .is_ok());
+
+        // The default initial value is 0.
+        assert!(matches!(
+            counters::create_strong("some_counter")
+                .with_lower_bound(1)
+                .validate(),
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            counters::create_strong("some_counter")
+                .with_lower_bound(10)
+                .with_upper_bound(0)
+                .validate(),
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            counters::create_weak("some_counter")
+                .with_concurrency_level(0)
+                .validate(),
+            Err(InfinispanError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn parse_counter_responses() {
+        assert_eq!(
+            10,
+            counters::get("some_counter")
+                .parse_response(StatusCode::OK, "10")
+                .unwrap()
+        );
+        assert_eq!(
+            Some(11),
+            counters::increment("some_counter")
+                .parse_response(StatusCode::OK, "11")
+                .unwrap()
+        );
+        assert_eq!(
+            None,
+            counters::decrement("some_counter")
+                .parse_response(StatusCode::NO_CONTENT, "")
+                .unwrap()
+        );
+        assert!(!counters::compare_and_set("some_counter", 1, 2)
+            .parse_response(StatusCode::OK, "false")
+            .unwrap());
+    }
+
+    #[test]
+    fn parse_counter_bound_errors() {
+        // The bounds are only read from the server by `run_typed`.
+        for message in ["Lower bound reached.", "ISPN000000: Upper bound reached."] {
+            let err = counters::increment("some_counter")
+                .parse_response(StatusCode::BAD_REQUEST, message)
+                .unwrap_err();
+
+            assert!(matches!(
+                err,
+                InfinispanError::CounterOutOfBounds {
+                    lower: None,
+                    upper: None
+                }
+            ));
+        }
+
+        let err = counters::decrement("some_counter")
+            .parse_response(StatusCode::NOT_FOUND, "")
+            .unwrap_err();
+
+        assert!(matches!(err, InfinispanError::UnexpectedResponse { .. }));
+    }
+
+    #[test]
+    fn parse_counter_config() {
+        let body = r#"{
+            "strong-counter": {
+                "name": "some_counter",
+                "initial-value": 5,
+                "storage": "PERSISTENT",
+                "lower-bound": 0,
+                "upper-bound": 10,
+                "some-new-attribute": true
+            }
+        }"#;
+
+        let counter = counters::get_config("some_counter")
+            .parse_response(StatusCode::OK, body)
+            .unwrap();
+
+        assert_eq!(CounterType::Strong, counter.counter_type());
+        assert_eq!(Some("some_counter"), counter.name());
+        assert_eq!(Some(CounterStorage::Persistent), counter.storage());
+
+        match counter {
+            Counter::Strong(counter) => {
+                assert_eq!(Some(0), counter.lower_bound());
+                assert_eq!(Some(10), counter.upper_bound());
+                assert_eq!(
+                    json!(true),
+                    counter.other_attributes()["some-new-attribute"]
+                );
+            }
+            Counter::Weak(_) => panic!("expected a strong counter"),
+        }
+    }
+}
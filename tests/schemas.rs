@@ -0,0 +1,59 @@
+mod helpers;
+
+#[cfg(test)]
+mod schemas {
+    use crate::helpers::*;
+    use infinispan::request::schemas;
+    use serial_test::serial;
+
+    const SCHEMA_NAME: &str = "person.proto";
+    const SCHEMA: &str = "package test;\n\nmessage Person {\n  optional string name = 1;\n}\n";
+
+    async fn cleanup() {
+        let _ = run(&schemas::delete(SCHEMA_NAME)).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn create_and_get() {
+        cleanup().await;
+
+        let resp = run(&schemas::create(SCHEMA_NAME, SCHEMA)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&schemas::get(SCHEMA_NAME)).await;
+        assert!(resp.status().is_success());
+        assert_eq!(SCHEMA, read_body(resp).await);
+
+        cleanup().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn update() {
+        cleanup().await;
+        let _ = run(&schemas::create(SCHEMA_NAME, SCHEMA)).await;
+
+        let updated = SCHEMA.replace("name", "full_name");
+        let resp = run(&schemas::update(SCHEMA_NAME, updated.clone())).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&schemas::get(SCHEMA_NAME)).await;
+        assert_eq!(updated, read_body(resp).await);
+
+        cleanup().await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn delete() {
+        cleanup().await;
+        let _ = run(&schemas::create(SCHEMA_NAME, SCHEMA)).await;
+
+        let resp = run(&schemas::delete(SCHEMA_NAME)).await;
+        assert!(resp.status().is_success());
+
+        let resp = run(&schemas::get(SCHEMA_NAME)).await;
+        assert_eq!(http::StatusCode::NOT_FOUND, resp.status());
+    }
+}
@@ -0,0 +1,26 @@
+mod helpers;
+
+#[cfg(test)]
+mod templates {
+    use crate::helpers::*;
+    use infinispan::request::caches::Cache;
+    use infinispan::request::templates;
+
+    const DIST_SYNC_TEMPLATE: &str = "org.infinispan.DIST_SYNC";
+
+    #[tokio::test]
+    async fn list() {
+        let templates = run_typed(&templates::list()).await;
+
+        assert!(templates
+            .iter()
+            .any(|template| template.name == DIST_SYNC_TEMPLATE));
+    }
+
+    #[tokio::test]
+    async fn get_config() {
+        let config = run_typed(&templates::get_config(DIST_SYNC_TEMPLATE)).await;
+
+        assert!(matches!(config, Cache::Distributed(_)));
+    }
+}